@@ -1,29 +1,566 @@
 // token caching/ expiration management
+//
+// 캐시 파일은 임시 파일에 쓴 뒤 rename으로 교체하고(원자적 쓰기), Unix에서는 0600 권한으로 만듭니다.
+// 여러 프로세스가 같은 캐시를 쓸 수 있으므로 `<캐시파일>.lock`에 advisory lock을 겁니다.
+// 설정에 암호화 키가 있으면 AES-256-GCM으로 암호화해서 저장합니다.
+// 한 파일에 app_key/환경/scope별 토큰을 함께 보관하므로 실전/모의 키를 바꿔도 다른 토큰을 쓰지 않습니다.
 
+use crate::auth::oauth::TOKEN_SCOPE;
+use crate::config::AppConfig;
+use crate::secret::Secret;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use chrono::{DateTime, Utc};
+use log::warn;
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::digest;
+use ring::rand::{SecureRandom, SystemRandom};
 use serde::{Deserialize, Serialize};
-use std::{fs, path::Path};
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
 
-#[derive(Debug, Serialize, Deserialize)]
+/// 암호화 알고리즘 표기 (파일 포맷 버전 겸용)
+const CIPHER_AES_256_GCM: &str = "aes-256-gcm";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CachedToken {
     pub access_token: String,
     pub expired_at: DateTime<Utc>,
 }
 
-pub fn load_cached_token(path: &str) -> Result<CachedToken, Box<dyn std::error::Error>> {
-    if !Path::new(path).exists() {
-        return Err("토큰 캐시 파일 없음".into());
+/// 암호화된 캐시 파일 내용
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedCache {
+    cipher: String,
+    nonce: String,
+    data: String,
+}
+
+/// 캐시 항목을 구분하는 키
+///
+/// app_key는 원문 대신 SHA-256 지문만 저장합니다.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct CacheKey {
+    pub app_key_fingerprint: String,
+    pub environment: String,
+    pub scope: String,
+}
+
+impl CacheKey {
+    pub fn new(app_key: &str, environment: impl Into<String>, scope: impl Into<String>) -> Self {
+        Self {
+            app_key_fingerprint: fingerprint(app_key),
+            environment: environment.into(),
+            scope: scope.into(),
+        }
+    }
+
+    /// 설정의 app_key, 접속 환경, 기본 scope로 만듭니다.
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(&config.app_key, config.environment.to_string(), TOKEN_SCOPE)
+    }
+}
+
+impl fmt::Display for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}/{}",
+            self.app_key_fingerprint, self.environment, self.scope
+        )
     }
-    let data = fs::read_to_string(path)?;
-    let token: CachedToken = serde_json::from_str(&data)?;
-    Ok(token)
 }
 
-pub fn save_cached_token(
-    path: &str,
-    token: &CachedToken,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let data = serde_json::to_string(token)?;
-    fs::write(path, data)?;
-    Ok(())
+/// app_key의 SHA-256 앞 8바이트를 hex로
+pub fn fingerprint(app_key: &str) -> String {
+    digest::digest(&digest::SHA256, app_key.as_bytes()).as_ref()[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// 캐시 파일에 저장되는 항목 하나
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub key: CacheKey,
+    pub token: CachedToken,
+}
+
+/// 캐시 파일 전체 내용 (암호화 시 이 JSON을 암호화)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    entries: Vec<CacheEntry>,
+}
+
+impl CacheFile {
+    /// 만료된 항목을 지우고 지운 개수를 돌려줍니다.
+    fn gc(&mut self, now: DateTime<Utc>) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.token.expired_at > now);
+        before - self.entries.len()
+    }
+}
+
+/// 토큰 캐시 파일
+///
+/// 한 파일에 (app_key 지문, 환경, scope)별로 여러 토큰을 저장하며,
+/// `load`/`save`/`clear`는 `key`에 해당하는 항목만 다룹니다.
+/// 저장할 때마다 만료된 항목은 정리됩니다.
+///
+/// `encryption_key`는 32바이트 키를 base64로 인코딩한 문자열입니다.
+/// (예: `openssl rand -base64 32`)
+#[derive(Debug, Clone)]
+pub struct TokenCache {
+    path: String,
+    key: CacheKey,
+    encryption_key: Option<Secret>,
+}
+
+impl TokenCache {
+    /// 평문으로 저장하는 캐시
+    pub fn new(path: impl Into<String>, key: CacheKey) -> Self {
+        Self {
+            path: path.into(),
+            key,
+            encryption_key: None,
+        }
+    }
+
+    /// `token_cache_file`과 `token_cache_key` 설정으로 만듭니다.
+    pub fn from_config(config: &AppConfig) -> Self {
+        let cache = Self::new(
+            config.token_cache_file.clone(),
+            CacheKey::from_config(config),
+        );
+        match &config.token_cache_key {
+            Some(key) => cache.with_encryption_key(key.clone()),
+            None => cache,
+        }
+    }
+
+    /// 저장 시 암호화할 키 (base64 인코딩된 32바이트)
+    pub fn with_encryption_key(mut self, key: impl Into<Secret>) -> Self {
+        self.encryption_key = Some(key.into());
+        self
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn key(&self) -> &CacheKey {
+        &self.key
+    }
+
+    /// 이 캐시 키의 토큰 (만료 여부와 무관)
+    pub fn load(&self) -> Result<CachedToken, Box<dyn std::error::Error>> {
+        if !Path::new(&self.path).exists() {
+            return Err("토큰 캐시 파일 없음".into());
+        }
+        let _lock = self.lock(false)?;
+        self.read()?
+            .entries
+            .into_iter()
+            .find(|e| e.key == self.key)
+            .map(|e| e.token)
+            .ok_or_else(|| format!("캐시에 토큰 없음: {}", self.key).into())
+    }
+
+    /// 파일에 저장된 모든 항목
+    pub fn entries(&self) -> Result<Vec<CacheEntry>, Box<dyn std::error::Error>> {
+        if !Path::new(&self.path).exists() {
+            return Ok(Vec::new());
+        }
+        let _lock = self.lock(false)?;
+        Ok(self.read()?.entries)
+    }
+
+    /// 이 캐시 키의 토큰을 저장(교체)하고 만료된 항목을 정리합니다.
+    pub fn save(&self, token: &CachedToken) -> Result<(), Box<dyn std::error::Error>> {
+        let _lock = self.lock(true)?;
+        let mut file = self.read_or_default()?;
+        file.entries.retain(|e| e.key != self.key);
+        file.gc(Utc::now());
+        file.entries.push(CacheEntry {
+            key: self.key.clone(),
+            token: token.clone(),
+        });
+        self.write(&file)
+    }
+
+    /// 이 캐시 키의 토큰을 지웁니다. 항목이 모두 없어지면 파일도 지웁니다.
+    pub fn clear(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let _lock = self.lock(true)?;
+        let mut file = self.read_or_default()?;
+        file.entries.retain(|e| e.key != self.key);
+        if file.entries.is_empty() {
+            return match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(Box::new(e)),
+                _ => Ok(()),
+            };
+        }
+        self.write(&file)
+    }
+
+    /// 만료된 항목을 모두 정리하고 지운 개수를 돌려줍니다.
+    pub fn gc(&self) -> Result<usize, Box<dyn std::error::Error>> {
+        if !Path::new(&self.path).exists() {
+            return Ok(0);
+        }
+        let _lock = self.lock(true)?;
+        let mut file = self.read()?;
+        let removed = file.gc(Utc::now());
+        if removed > 0 {
+            self.write(&file)?;
+        }
+        Ok(removed)
+    }
+
+    /// lock을 잡은 상태에서 호출해야 합니다.
+    fn read(&self) -> Result<CacheFile, Box<dyn std::error::Error>> {
+        Ok(serde_json::from_slice(&self.read_plain()?)?)
+    }
+
+    /// 복호화한 파일 내용. lock을 잡은 상태에서 호출해야 합니다.
+    fn read_plain(&self) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let data = fs::read_to_string(&self.path)?;
+        let plain = match serde_json::from_str::<EncryptedCache>(&data) {
+            Ok(encrypted) => {
+                let key = self
+                    .cipher_key()?
+                    .ok_or("암호화된 토큰 캐시지만 암호화 키가 없음")?;
+                decrypt(&key, &encrypted)?
+            }
+            // 평문 캐시 (암호화 키를 새로 설정한 경우 다음 저장부터 암호화됨)
+            Err(_) => data.into_bytes(),
+        };
+        Ok(plain)
+    }
+
+    /// 파일이 없거나 구버전 단일 토큰 형식이면 빈 캐시를 돌려줍니다.
+    ///
+    /// 그 외에 읽을 수 없는 파일(키 불일치, 손상 등)은 다른 항목을 덮어쓰지 않도록 에러로 돌려줍니다.
+    fn read_or_default(&self) -> Result<CacheFile, Box<dyn std::error::Error>> {
+        if !Path::new(&self.path).exists() {
+            return Ok(CacheFile::default());
+        }
+        let plain = self.read_plain()?;
+        match serde_json::from_slice::<CacheFile>(&plain) {
+            Ok(file) => Ok(file),
+            Err(_) if serde_json::from_slice::<CachedToken>(&plain).is_ok() => {
+                warn!("구버전 토큰 캐시를 새 형식으로 교체합니다: {}", self.path);
+                Ok(CacheFile::default())
+            }
+            Err(e) => Err(format!("토큰 캐시 파싱 실패 ({}): {}", self.path, e).into()),
+        }
+    }
+
+    /// 임시 파일에 쓴 뒤 rename. lock을 잡은 상태에서 호출해야 합니다.
+    fn write(&self, file: &CacheFile) -> Result<(), Box<dyn std::error::Error>> {
+        let plain = serde_json::to_vec(file)?;
+        let data = match self.cipher_key()? {
+            Some(key) => serde_json::to_vec(&encrypt(&key, &plain)?)?,
+            None => plain,
+        };
+
+        // 같은 디렉터리의 임시 파일에 쓴 뒤 rename (같은 파일시스템이어야 원자적)
+        let tmp_path = format!("{}.tmp.{}", self.path, std::process::id());
+        let result = (|| -> std::io::Result<()> {
+            let mut file = open_private(&tmp_path, true)?;
+            file.write_all(&data)?;
+            file.sync_all()?;
+            fs::rename(&tmp_path, &self.path)
+        })();
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp_path);
+        }
+        Ok(result?)
+    }
+
+    /// `<캐시파일>.lock`에 advisory lock을 겁니다. 반환된 File이 drop되면 해제됩니다.
+    ///
+    /// 캐시 파일 자체는 rename으로 교체되므로 별도 lock 파일을 사용합니다.
+    fn lock(&self, exclusive: bool) -> std::io::Result<File> {
+        let file = open_private(&format!("{}.lock", self.path), false)?;
+        if exclusive {
+            file.lock()?;
+        } else {
+            file.lock_shared()?;
+        }
+        Ok(file)
+    }
+
+    fn cipher_key(&self) -> Result<Option<LessSafeKey>, Box<dyn std::error::Error>> {
+        let Some(encoded) = &self.encryption_key else {
+            return Ok(None);
+        };
+        let bytes = BASE64
+            .decode(encoded.expose().trim())
+            .map_err(|e| format!("토큰 캐시 암호화 키 디코딩 실패: {}", e))?;
+        let key = UnboundKey::new(&AES_256_GCM, &bytes).map_err(|_| {
+            format!(
+                "토큰 캐시 암호화 키는 32바이트여야 함 (현재 {}바이트)",
+                bytes.len()
+            )
+        })?;
+        Ok(Some(LessSafeKey::new(key)))
+    }
+}
+
+/// 소유자만 읽고 쓸 수 있는 파일을 엽니다. (Unix 0600)
+fn open_private(path: &str, truncate: bool) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(truncate);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+fn encrypt(key: &LessSafeKey, plain: &[u8]) -> Result<EncryptedCache, Box<dyn std::error::Error>> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| "nonce 생성 실패")?;
+    let mut data = plain.to_vec();
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| "토큰 캐시 암호화 실패")?;
+    Ok(EncryptedCache {
+        cipher: CIPHER_AES_256_GCM.to_string(),
+        nonce: BASE64.encode(nonce),
+        data: BASE64.encode(data),
+    })
+}
+
+fn decrypt(
+    key: &LessSafeKey,
+    encrypted: &EncryptedCache,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if encrypted.cipher != CIPHER_AES_256_GCM {
+        return Err(format!("지원하지 않는 캐시 암호화 방식: {}", encrypted.cipher).into());
+    }
+    let nonce: [u8; NONCE_LEN] = BASE64
+        .decode(&encrypted.nonce)?
+        .try_into()
+        .map_err(|_| "nonce 길이 오류")?;
+    let mut data = BASE64.decode(&encrypted.data)?;
+    let plain = key
+        .open_in_place(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| "토큰 캐시 복호화 실패 (키가 다르거나 파일이 손상됨)")?;
+    Ok(plain.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_path;
+
+    const TEST_KEY: &str = "MDEyMzQ1Njc4OWFiY2RlZjAxMjM0NTY3ODlhYmNkZWY="; // "0123456789abcdef" x2
+
+    fn test_key() -> CacheKey {
+        CacheKey::new("app-key", "prod", TOKEN_SCOPE)
+    }
+
+    fn sample_token() -> CachedToken {
+        CachedToken {
+            access_token: "secret-bearer-token".to_string(),
+            expired_at: Utc::now() + chrono::Duration::hours(1),
+        }
+    }
+
+    #[test]
+    fn test_plain_roundtrip_and_no_temp_left() {
+        let path = temp_path("cache_plain.json");
+        let cache = TokenCache::new(&path, test_key());
+        let token = sample_token();
+        cache.save(&token).unwrap();
+        assert_eq!(cache.load().unwrap(), token);
+        assert!(!Path::new(&format!("{}.tmp.{}", path, std::process::id())).exists());
+        cache.clear().unwrap();
+        cache.clear().unwrap();
+        assert!(cache.load().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_file_permissions_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let path = temp_path("cache_perm.json");
+        let cache = TokenCache::new(&path, test_key());
+        cache.save(&sample_token()).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_encrypted_roundtrip() {
+        let path = temp_path("cache_enc.json");
+        let cache = TokenCache::new(&path, test_key()).with_encryption_key(TEST_KEY);
+        let token = sample_token();
+        cache.save(&token).unwrap();
+
+        // 파일에 토큰 평문이 남지 않아야 함
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("secret-bearer-token"));
+        assert_eq!(cache.load().unwrap(), token);
+
+        // 키 없이 또는 다른 키로는 읽을 수 없음
+        assert!(TokenCache::new(&path, test_key()).load().is_err());
+        let other = BASE64.encode([7u8; 32]);
+        assert!(
+            TokenCache::new(&path, test_key())
+                .with_encryption_key(other)
+                .load()
+                .is_err()
+        );
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_invalid_key_is_error() {
+        let path = temp_path("cache_badkey.json");
+        let cache =
+            TokenCache::new(&path, test_key()).with_encryption_key(BASE64.encode([1u8; 16]));
+        assert!(cache.save(&sample_token()).is_err());
+        assert!(!Path::new(&path).exists());
+    }
+
+    #[test]
+    fn test_concurrent_writers_never_tear() {
+        let path = temp_path("cache_concurrent.json");
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let key = CacheKey::new(&format!("app-key-{}", i), "prod", TOKEN_SCOPE);
+                    let cache = TokenCache::new(&path, key);
+                    for _ in 0..20 {
+                        let token = CachedToken {
+                            access_token: format!("token-{}-{}", i, "x".repeat(512)),
+                            expired_at: Utc::now() + chrono::Duration::hours(1),
+                        };
+                        cache.save(&token).unwrap();
+                        // 읽을 때마다 완전한 JSON이어야 함
+                        cache.load().unwrap();
+                    }
+                })
+            })
+            .collect();
+        for h in handles {
+            h.join().unwrap();
+        }
+        // read-modify-write가 직렬화되어 항목이 유실되지 않아야 함
+        assert_eq!(
+            TokenCache::new(&path, test_key()).entries().unwrap().len(),
+            8
+        );
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_entries_are_isolated_by_key() {
+        let path = temp_path("cache_keyed.json");
+        let prod = TokenCache::new(&path, CacheKey::new("key-a", "prod", TOKEN_SCOPE));
+        let demo = TokenCache::new(&path, CacheKey::new("key-a", "demo", TOKEN_SCOPE));
+        let other_app = TokenCache::new(&path, CacheKey::new("key-b", "prod", TOKEN_SCOPE));
+
+        let mut token = sample_token();
+        prod.save(&token).unwrap();
+        token.access_token = "demo-token".to_string();
+        demo.save(&token).unwrap();
+
+        assert_eq!(prod.load().unwrap().access_token, "secret-bearer-token");
+        assert_eq!(demo.load().unwrap().access_token, "demo-token");
+        assert!(other_app.load().is_err());
+
+        // app_key 원문은 파일에 저장되지 않음
+        let raw = fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("key-a"));
+        assert!(raw.contains(&fingerprint("key-a")));
+
+        // 한 항목만 지우면 나머지는 유지
+        prod.clear().unwrap();
+        assert!(prod.load().is_err());
+        assert_eq!(demo.load().unwrap().access_token, "demo-token");
+        demo.clear().unwrap();
+        assert!(!Path::new(&path).exists());
+    }
+
+    #[test]
+    fn test_stale_entries_are_garbage_collected() {
+        let path = temp_path("cache_gc.json");
+        let stale = TokenCache::new(&path, CacheKey::new("old-key", "prod", TOKEN_SCOPE));
+        stale
+            .save(&CachedToken {
+                access_token: "expired".to_string(),
+                expired_at: Utc::now() - chrono::Duration::minutes(1),
+            })
+            .unwrap();
+        assert_eq!(stale.entries().unwrap().len(), 1);
+
+        // 다른 키로 저장할 때 만료 항목 정리
+        let cache = TokenCache::new(&path, test_key());
+        cache.save(&sample_token()).unwrap();
+        let entries = cache.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, test_key());
+
+        // 명시적 gc
+        stale
+            .save(&CachedToken {
+                access_token: "expired".to_string(),
+                expired_at: Utc::now() - chrono::Duration::minutes(1),
+            })
+            .unwrap();
+        assert_eq!(cache.gc().unwrap(), 1);
+        assert_eq!(cache.gc().unwrap(), 0);
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_unreadable_file_is_not_overwritten() {
+        // 다른 키로 암호화된 캐시: 저장/삭제 모두 실패하고 기존 항목은 그대로
+        let path = temp_path("cache_foreign.json");
+        let owner = TokenCache::new(&path, CacheKey::new("key-a", "prod", TOKEN_SCOPE))
+            .with_encryption_key(TEST_KEY);
+        owner.save(&sample_token()).unwrap();
+        let before = fs::read_to_string(&path).unwrap();
+
+        let other =
+            TokenCache::new(&path, test_key()).with_encryption_key(BASE64.encode([7u8; 32]));
+        assert!(other.save(&sample_token()).is_err());
+        assert!(other.clear().is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), before);
+        assert_eq!(owner.entries().unwrap().len(), 1);
+        owner.clear().unwrap();
+
+        // 손상된 파일
+        let path = temp_path("cache_corrupt.json");
+        fs::write(&path, "{\"entries\": [").unwrap();
+        let cache = TokenCache::new(&path, test_key());
+        assert!(cache.save(&sample_token()).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"entries\": [");
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_legacy_single_token_file_is_replaced() {
+        let path = temp_path("cache_legacy.json");
+        fs::write(&path, serde_json::to_string(&sample_token()).unwrap()).unwrap();
+        let cache = TokenCache::new(&path, test_key());
+        assert!(cache.load().is_err());
+
+        cache.save(&sample_token()).unwrap();
+        assert_eq!(cache.entries().unwrap().len(), 1);
+        cache.clear().unwrap();
+    }
 }
@@ -0,0 +1,257 @@
+// 인증(토큰/접속키 발급, 폐기) 에러 타입
+// LS 에러 응답(HTTP 상태, rsp_cd/rsp_msg)을 원인별 variant로 구분하고, 로그용 토큰 마스킹을 제공합니다.
+
+use crate::auth::rate_limit::RateLimited;
+use crate::constant::{LS_RSP_CD_AUTH, LS_RSP_CD_RATE_LIMITED};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+
+/// 서버가 Retry-After 없이 요청 제한을 알린 경우 기본 대기시간
+pub const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+/// 로그에서 값을 가릴 응답 필드
+const SECRET_FIELDS: &[&str] = &[
+    "access_token",
+    "approval_key",
+    "token",
+    "appkey",
+    "appsecretkey",
+    "secretkey",
+];
+
+#[derive(Debug)]
+pub enum AuthError {
+    /// 연결 실패, 타임아웃 등 전송 계층 에러
+    Network(reqwest::Error),
+    /// app_key/app_secret 오류 등 인증 정보 거부
+    InvalidCredentials { rsp_cd: String, rsp_msg: String },
+    /// 발급 요청 제한 (로컬 가드 또는 서버 응답)
+    RateLimited { retry_after: Duration },
+    /// 그 밖의 LS 에러 응답
+    Api {
+        status: u16,
+        rsp_cd: String,
+        rsp_msg: String,
+    },
+    /// 성공 응답이지만 본문을 해석할 수 없음
+    InvalidResponse(String),
+    /// 토큰 캐시 읽기/쓰기 실패
+    Cache(String),
+    /// `TokenManager::revoke()` 이후의 토큰 요청
+    Revoked,
+}
+
+/// LS 에러 응답 본문
+#[derive(Debug, Default, Deserialize)]
+struct LsErrorBody {
+    #[serde(default)]
+    rsp_cd: String,
+    #[serde(default)]
+    rsp_msg: String,
+}
+
+impl AuthError {
+    /// 에러 응답(HTTP 상태 + 본문)을 variant로 분류합니다.
+    ///
+    /// HTTP 상태와 rsp_cd(`LS_RSP_CD_RATE_LIMITED`, `LS_RSP_CD_AUTH`)로 판별하고,
+    /// 본문에 rsp_cd가 없을 때만 rsp_msg 내용을 봅니다.
+    pub fn from_response(status: StatusCode, body: &str, retry_after: Option<Duration>) -> Self {
+        let LsErrorBody { rsp_cd, rsp_msg } = serde_json::from_str(body).unwrap_or_default();
+        let msg = rsp_msg.to_lowercase();
+        let by_msg =
+            |keywords: &[&str]| rsp_cd.is_empty() && keywords.iter().any(|k| msg.contains(k));
+
+        if status == StatusCode::TOO_MANY_REQUESTS
+            || LS_RSP_CD_RATE_LIMITED.contains(&rsp_cd.as_str())
+            || by_msg(&["초과", "too many", "건수"])
+        {
+            return AuthError::RateLimited {
+                retry_after: retry_after.unwrap_or(DEFAULT_RETRY_AFTER),
+            };
+        }
+        if matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+            || LS_RSP_CD_AUTH.contains(&rsp_cd.as_str())
+            || by_msg(&["appkey", "appsecret", "secretkey", "인증", "유효하지"])
+        {
+            return AuthError::InvalidCredentials { rsp_cd, rsp_msg };
+        }
+        AuthError::Api {
+            status: status.as_u16(),
+            rsp_cd,
+            rsp_msg: if rsp_msg.is_empty() {
+                redact(body)
+            } else {
+                rsp_msg
+            },
+        }
+    }
+
+    /// 잠시 후 같은 요청을 다시 보내면 성공할 수 있는 에러인지
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            AuthError::Network(_) | AuthError::RateLimited { .. } => true,
+            AuthError::Api { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::Network(e) => write!(f, "네트워크 에러: {}", e),
+            AuthError::InvalidCredentials { rsp_cd, rsp_msg } => {
+                write!(f, "인증 정보 거부: [{}] {}", rsp_cd, rsp_msg)
+            }
+            AuthError::RateLimited { retry_after } => write!(
+                f,
+                "토큰 발급 요청 제한: {}초 후 재시도하세요",
+                retry_after.as_secs().max(1)
+            ),
+            AuthError::Api {
+                status,
+                rsp_cd,
+                rsp_msg,
+            } => write!(f, "LS API 에러: HTTP {} [{}] {}", status, rsp_cd, rsp_msg),
+            AuthError::InvalidResponse(msg) => write!(f, "응답 해석 실패: {}", msg),
+            AuthError::Cache(msg) => write!(f, "토큰 캐시 에러: {}", msg),
+            AuthError::Revoked => write!(f, "토큰이 폐기되어 더 이상 발급하지 않습니다"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AuthError::Network(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for AuthError {
+    fn from(e: reqwest::Error) -> Self {
+        AuthError::Network(e)
+    }
+}
+
+impl From<RateLimited> for AuthError {
+    fn from(e: RateLimited) -> Self {
+        AuthError::RateLimited {
+            retry_after: e.retry_after,
+        }
+    }
+}
+
+/// 응답 본문의 토큰/키 값을 가립니다. (로그 출력용)
+///
+/// JSON이 아니면 어느 부분이 토큰인지 알 수 없으므로 본문 전체를 가립니다.
+pub fn redact(body: &str) -> String {
+    let Ok(mut value) = serde_json::from_str::<Value>(body) else {
+        return mask(body);
+    };
+    redact_value(&mut value);
+    value.to_string()
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if SECRET_FIELDS.contains(&key.as_str())
+                    && let Value::String(s) = v
+                {
+                    *s = mask(s);
+                } else {
+                    redact_value(v);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+/// 앞 4자만 남기고 가림
+pub fn mask(secret: &str) -> String {
+    let prefix: String = secret.chars().take(4).collect();
+    format!("{}****", prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_error_responses() {
+        let body = r#"{"rsp_cd":"IGW00105","rsp_msg":"유효하지 않은 AppSecret입니다."}"#;
+        assert!(matches!(
+            AuthError::from_response(StatusCode::FORBIDDEN, body, None),
+            AuthError::InvalidCredentials { rsp_cd, .. } if rsp_cd == "IGW00105"
+        ));
+
+        let body = r#"{"rsp_cd":"IGW00201","rsp_msg":"초당 전송건수를 초과하였습니다."}"#;
+        match AuthError::from_response(StatusCode::BAD_REQUEST, body, None) {
+            AuthError::RateLimited { retry_after } => assert_eq!(retry_after, DEFAULT_RETRY_AFTER),
+            other => panic!("RateLimited가 아님: {:?}", other),
+        }
+        assert!(matches!(
+            AuthError::from_response(
+                StatusCode::TOO_MANY_REQUESTS,
+                "",
+                Some(Duration::from_secs(7))
+            ),
+            AuthError::RateLimited { retry_after } if retry_after.as_secs() == 7
+        ));
+
+        // HTTP 200이어도 rsp_cd로 판별
+        let body = r#"{"rsp_cd":"IGW00121","rsp_msg":"토큰 오류"}"#;
+        assert!(matches!(
+            AuthError::from_response(StatusCode::OK, body, None),
+            AuthError::InvalidCredentials { rsp_cd, .. } if rsp_cd == "IGW00121"
+        ));
+
+        // 알 수 없는 rsp_cd는 메시지에 키워드가 있어도 Api
+        let body = r#"{"rsp_cd":"IGW00999","rsp_msg":"조회 건수를 초과했습니다. appkey 확인"}"#;
+        assert!(matches!(
+            AuthError::from_response(StatusCode::BAD_REQUEST, body, None),
+            AuthError::Api { status: 400, rsp_cd, .. } if rsp_cd == "IGW00999"
+        ));
+
+        // rsp_cd가 없으면 메시지로 판별
+        let body = r#"{"rsp_msg":"Too many requests"}"#;
+        assert!(matches!(
+            AuthError::from_response(StatusCode::BAD_REQUEST, body, None),
+            AuthError::RateLimited { .. }
+        ));
+
+        // JSON이 아닌 본문은 가린 채로 담음
+        let err = AuthError::from_response(StatusCode::INTERNAL_SERVER_ERROR, "oops", None);
+        assert!(
+            matches!(&err, AuthError::Api { status: 500, rsp_msg, .. } if rsp_msg == "oops****")
+        );
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn test_redact_hides_tokens() {
+        let body = r#"{"access_token":"eyJhbGciOiJIUzUxMiJ9.secret","expires_in":86400,"nested":{"approval_key":"abcdefgh"}}"#;
+        let redacted = redact(body);
+        assert!(!redacted.contains("secret"));
+        assert!(!redacted.contains("abcdefgh"));
+        assert!(redacted.contains("eyJh****"));
+        assert!(redacted.contains("86400"));
+    }
+
+    #[test]
+    fn test_redact_masks_non_json_body() {
+        let body = "access_token=eyJhbGciOiJIUzUxMiJ9.secret&expires_in=86400";
+        let redacted = redact(body);
+        assert!(!redacted.contains("secret"));
+        assert!(!redacted.contains("eyJhbGci"));
+        assert_eq!(redacted, "acce****");
+    }
+}
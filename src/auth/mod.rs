@@ -1,3 +1,6 @@
 pub mod cache;
+pub mod error;
 pub mod oauth;
+pub mod rate_limit;
+pub mod token_manager;
 pub mod ws_auth;
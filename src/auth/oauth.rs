@@ -1,8 +1,16 @@
-use crate::auth::cache::{CachedToken, load_cached_token, save_cached_token};
+use crate::auth::cache::{CachedToken, TokenCache};
+use crate::auth::error::{AuthError, mask, redact};
+use crate::auth::rate_limit::IssuanceGuard;
 use crate::config::AppConfig;
+use crate::constant::LS_RSP_CD_OK;
 use chrono::{Duration, Utc};
-use reqwest::Client;
+use log::{debug, info, warn};
+use reqwest::{Client, Response};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+
+/// 토큰 발급 scope ("oob" 고정)
+pub const TOKEN_SCOPE: &str = "oob";
 
 #[derive(Debug, Serialize, Deserialize)]
 struct TokenResponse {
@@ -12,88 +20,535 @@ struct TokenResponse {
     token_type: String, // bearer
 }
 
-pub async fn get_access_token(config: &AppConfig) -> Result<String, Box<dyn std::error::Error>> {
+#[derive(Debug, Deserialize)]
+struct RevokeResponse {
+    #[serde(default)]
+    rsp_cd: String,
+    #[serde(default)]
+    rsp_msg: String,
+}
+
+/// 캐시된 토큰이 유효하면 그대로, 아니면 새로 발급받아 캐시에 저장합니다.
+pub async fn get_access_token(client: &Client, config: &AppConfig) -> Result<String, AuthError> {
     // 1. 캐시된 토큰이 있으면 만료 전까지 재사용
-    if let Ok(cached) = load_cached_token(&config.token_cache_file) {
-        if cached.expired_at > Utc::now() {
-            println!("Cached token 사용: 만료시각={}", cached.expired_at);
-            return Ok(cached.access_token);
-        }
+    let cache = TokenCache::from_config(config);
+    if let Ok(cached) = cache.load()
+        && cached.expired_at > Utc::now()
+    {
+        debug!("Cached token 사용: 만료시각={}", cached.expired_at);
+        return Ok(cached.access_token);
+    }
+
+    // 2. 신규 발급
+    let cached = issue_access_token(client, config).await?;
+
+    // 3. 캐시 저장
+    if let Err(e) = cache.save(&cached) {
+        warn!("토큰 캐시 저장 실패: {}", e);
+    }
+
+    info!("신규 토큰 발급, 만료시각={}", cached.expired_at);
+    Ok(cached.access_token)
+}
+
+/// 응답 본문을 읽어 로그로 남기고, 실패 상태면 `AuthError`로 분류합니다.
+pub(crate) async fn read_response(resp: Response, what: &str) -> Result<String, AuthError> {
+    let status = resp.status();
+    let retry_after = resp
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(std::time::Duration::from_secs);
+    let text = resp.text().await?;
+    debug!("LS {} 응답 (HTTP {}): {}", what, status, redact(&text));
+
+    if !status.is_success() {
+        let err = AuthError::from_response(status, &text, retry_after);
+        warn!("LS {} 실패: {}", what, err);
+        return Err(err);
     }
+    Ok(text)
+}
 
-    // 2. API 요청 (LS증권: x-www-form-urlencoded)
-    let client = Client::new();
+/// 캐시를 거치지 않고 `/oauth2/token`으로 토큰을 새로 발급받습니다.
+///
+/// 발급 최소 간격 전에 호출하면 요청을 보내지 않고 `AuthError::RateLimited`를 돌려줍니다.
+/// 발급에 실패하면 간격 기록을 되돌리므로 바로 다시 시도할 수 있습니다.
+pub async fn issue_access_token(
+    client: &Client,
+    config: &AppConfig,
+) -> Result<CachedToken, AuthError> {
+    let reservation = IssuanceGuard::from_config(config).reserve()?;
+    let token = request_access_token(client, config).await?;
+    reservation.commit();
+    Ok(token)
+}
+
+/// `issue_access_token`과 같지만 발급 최소 간격이 남아 있으면 기다렸다가 발급받습니다.
+pub async fn issue_access_token_when_allowed(
+    client: &Client,
+    config: &AppConfig,
+) -> Result<CachedToken, AuthError> {
+    let reservation = IssuanceGuard::from_config(config).wait().await;
+    let token = request_access_token(client, config).await?;
+    reservation.commit();
+    Ok(token)
+}
+
+async fn request_access_token(
+    client: &Client,
+    config: &AppConfig,
+) -> Result<CachedToken, AuthError> {
+    // API 요청 (LS증권: x-www-form-urlencoded)
     let params = [
         ("grant_type", "client_credentials"),
         ("appkey", &config.app_key),
-        ("appsecretkey", &config.app_secret),
-        ("scope", "oob"),
+        ("appsecretkey", config.app_secret.expose()),
+        ("scope", TOKEN_SCOPE),
     ];
-    let token_url = format!("{}/oauth2/token", config.token_url);
+    let token_url = format!("{}/oauth2/token", config.rest_base_url());
 
-    let resp = match client
+    let resp = client
         .post(&token_url)
         .header("Content-Type", "application/x-www-form-urlencoded")
         .form(&params)
         .send()
-        .await
-    {
-        Ok(r) => r,
-        Err(e) => {
-            println!("HTTP 요청 실패: {}", e);
-            return Err(Box::new(e));
-        }
-    };
-
-    let text = match resp.text().await {
-        Ok(t) => t,
-        Err(e) => {
-            println!("응답 본문 읽기 실패: {}", e);
-            return Err(Box::new(e));
-        }
-    };
-    println!("LS 토큰 응답 원문: {}", text);
+        .await?;
+    let text = read_response(resp, "토큰 발급").await?;
 
-    let token: TokenResponse = match serde_json::from_str(&text) {
-        Ok(token) => token,
-        Err(e) => {
-            println!("LS API 토큰 파싱 실패: {}", e);
-            return Err(Box::new(e));
+    let token: TokenResponse = serde_json::from_str(&text).map_err(|e| {
+        // 200이지만 토큰 대신 에러 본문(rsp_cd/rsp_msg)이 오는 경우
+        match AuthError::from_response(reqwest::StatusCode::OK, &text, None) {
+            AuthError::Api { rsp_cd, .. } if rsp_cd.is_empty() => {
+                AuthError::InvalidResponse(format!("토큰 파싱 실패: {}", e))
+            }
+            other => other,
         }
-    };
+    })?;
 
-    // 3. 만료시각 계산 (expire_in: 초 단위 문자열)
+    // 만료시각 계산 (expire_in: 초 단위)
     let expire_secs = token.expires_in as i64;
     let expired_at = Utc::now() + Duration::seconds(expire_secs);
+    debug!(
+        "토큰 발급: {} 만료시각={}",
+        mask(&token.access_token),
+        expired_at
+    );
 
-    // 4. 캐시 저장
-    let cached = CachedToken {
-        access_token: token.access_token.clone(),
+    Ok(CachedToken {
+        access_token: token.access_token,
         expired_at,
+    })
+}
+
+/// `/oauth2/revoke`로 접근 토큰을 폐기합니다.
+pub async fn revoke_access_token(
+    client: &Client,
+    config: &AppConfig,
+    token: &str,
+) -> Result<(), AuthError> {
+    let params = [
+        ("appkey", config.app_key.as_str()),
+        ("appsecretkey", config.app_secret.expose()),
+        ("token_type_hint", "access_token"),
+        ("token", token),
+    ];
+    let revoke_url = format!("{}/oauth2/revoke", config.rest_base_url());
+
+    let resp = client
+        .post(&revoke_url)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .form(&params)
+        .send()
+        .await?;
+    let text = read_response(resp, "토큰 폐기").await?;
+
+    // 본문이 비어 있거나 rsp_cd가 없으면 HTTP 상태만으로 판단
+    if let Ok(body) = serde_json::from_str::<RevokeResponse>(&text)
+        && !body.rsp_cd.is_empty()
+        && body.rsp_cd != LS_RSP_CD_OK
+    {
+        return Err(AuthError::Api {
+            status: 200,
+            rsp_cd: body.rsp_cd,
+            rsp_msg: body.rsp_msg,
+        });
+    }
+    Ok(())
+}
+
+/// 캐시 파일의 토큰을 폐기하고 캐시를 지웁니다.
+///
+/// 캐시가 없으면 `Ok(false)`, 폐기했으면 `Ok(true)`를 돌려줍니다.
+/// 이미 만료된 토큰은 API를 호출하지 않고 캐시만 지웁니다.
+pub async fn revoke_cached_token(client: &Client, config: &AppConfig) -> Result<bool, AuthError> {
+    let cache = TokenCache::from_config(config);
+    let Some(cached) = cache.load().ok() else {
+        return Ok(false);
     };
-    if let Err(e) = save_cached_token(&config.token_cache_file, &cached) {
-        println!("토큰 캐시 저장 실패: {}", e);
+    if cached.expired_at > Utc::now() {
+        revoke_access_token(client, config, &cached.access_token).await?;
+        info!("토큰 폐기 완료: {}", mask(&cached.access_token));
     }
+    cache.clear().map_err(|e| AuthError::Cache(e.to_string()))?;
+    Ok(true)
+}
 
-    println!("신규 토큰 발급, 만료시각={}", expired_at);
-    Ok(token.access_token)
+/// `shutdown` future가 끝나면 캐시된 토큰을 폐기하는 종료 훅
+///
+/// ```ignore
+/// tokio::spawn(revoke_on_shutdown(client.clone(), config.clone(), tokio::signal::ctrl_c()));
+/// ```
+pub async fn revoke_on_shutdown<F: Future>(
+    client: Client,
+    config: AppConfig,
+    shutdown: F,
+) -> Result<bool, AuthError> {
+    shutdown.await;
+    info!("종료 요청 수신, 토큰을 폐기합니다.");
+    revoke_cached_token(&client, &config).await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::config::AppConfig;
+    use crate::environment::Environment;
+    use crate::test_support::{self, temp_path};
     use tokio;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     // 실제 API 호출이므로, 환경변수와 네트워크가 필요합니다.
     #[tokio::test]
     async fn test_get_access_token() {
         let config = AppConfig::from_env();
-        let result = get_access_token(&config).await;
+        let result = get_access_token(&config.http_client().unwrap(), &config).await;
         match &result {
             Ok(token) => println!("AccessToken: {}", token),
             Err(e) => println!("AccessToken 발급 실패: {}", e),
         }
         assert!(result.is_ok() || result.is_err());
     }
+
+    fn mock_config(server: &MockServer, name: &str) -> AppConfig {
+        let config = AppConfig {
+            app_key: "test-key".to_string(),
+            app_secret: "test-secret".into(),
+            token_cache_file: temp_path(&format!("oauth_{}.json", name)),
+            token_issue_interval_secs: 0,
+            ..test_support::mock_config(server)
+        };
+        TokenCache::from_config(&config)
+            .save(&CachedToken {
+                access_token: "tok-live".to_string(),
+                expired_at: Utc::now() + Duration::days(1),
+            })
+            .unwrap();
+        config
+    }
+
+    async fn mount_revoke(server: &MockServer, rsp_cd: &str) {
+        Mock::given(method("POST"))
+            .and(path("/oauth2/revoke"))
+            .and(body_string_contains("token=tok-live"))
+            .and(body_string_contains("token_type_hint=access_token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "rsp_cd": rsp_cd,
+                "rsp_msg": "응답",
+            })))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_revoke_cached_token_clears_cache() {
+        let server = MockServer::start().await;
+        mount_revoke(&server, "00000").await;
+        let config = mock_config(&server, "revoke");
+
+        let client = Client::new();
+        assert!(revoke_cached_token(&client, &config).await.unwrap());
+        assert!(TokenCache::from_config(&config).load().is_err());
+        // 캐시가 없으면 아무것도 하지 않음
+        assert!(!revoke_cached_token(&client, &config).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_failure_keeps_cache() {
+        let server = MockServer::start().await;
+        mount_revoke(&server, "IGW00105").await;
+        let config = mock_config(&server, "revoke_fail");
+
+        assert!(matches!(
+            revoke_cached_token(&Client::new(), &config).await,
+            Err(AuthError::Api { rsp_cd, .. }) if rsp_cd == "IGW00105"
+        ));
+        let cache = TokenCache::from_config(&config);
+        assert!(cache.load().is_ok());
+        cache.clear().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_revoke_on_shutdown() {
+        let server = MockServer::start().await;
+        mount_revoke(&server, "00000").await;
+        let config = mock_config(&server, "shutdown");
+        let cache = TokenCache::from_config(&config);
+
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let hook = tokio::spawn(revoke_on_shutdown(Client::new(), config, rx));
+        // 종료 신호 전에는 캐시 유지
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(cache.load().is_ok());
+
+        tx.send(()).unwrap();
+        assert!(hook.await.unwrap().unwrap());
+        assert!(cache.load().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_access_token_picks_entry_for_app_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .and(body_string_contains("appkey=other-key"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "tok-other",
+                "expires_in": 86400,
+                "scope": "oob",
+                "token_type": "Bearer",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let config = mock_config(&server, "keyed");
+
+        let client = Client::new();
+        // 같은 키는 캐시된 토큰 재사용
+        assert_eq!(
+            get_access_token(&client, &config).await.unwrap(),
+            "tok-live"
+        );
+
+        // 다른 app_key는 같은 캐시 파일을 써도 새로 발급
+        let other = AppConfig {
+            app_key: "other-key".to_string(),
+            ..config.clone()
+        };
+        assert_eq!(
+            get_access_token(&client, &other).await.unwrap(),
+            "tok-other"
+        );
+        assert_eq!(
+            get_access_token(&client, &other).await.unwrap(),
+            "tok-other"
+        );
+        assert_eq!(
+            get_access_token(&client, &config).await.unwrap(),
+            "tok-live"
+        );
+
+        let cache = TokenCache::from_config(&config);
+        assert_eq!(cache.entries().unwrap().len(), 2);
+        let _ = std::fs::remove_file(cache.path());
+    }
+
+    #[tokio::test]
+    async fn test_issue_is_rate_limited() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "tok-1",
+                "expires_in": 86400,
+                "scope": "oob",
+                "token_type": "Bearer",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let config = AppConfig {
+            token_issue_interval_secs: 3600,
+            ..mock_config(&server, "rate_limited")
+        };
+        let guard_file = format!(
+            "{}.{}.issued",
+            config.token_cache_file,
+            crate::auth::cache::fingerprint(&config.app_key)
+        );
+        let _ = std::fs::remove_file(&guard_file);
+
+        let client = Client::new();
+        issue_access_token(&client, &config).await.unwrap();
+        // 두 번째 요청은 서버로 나가지 않고 재시도 시간을 알려줌
+        let err = issue_access_token(&client, &config).await.unwrap_err();
+        match err {
+            AuthError::RateLimited { retry_after } => assert!(retry_after.as_secs() > 3500),
+            other => panic!("RateLimited가 아님: {:?}", other),
+        }
+
+        let _ = std::fs::remove_file(&guard_file);
+        let _ = std::fs::remove_file(&config.token_cache_file);
+    }
+
+    #[tokio::test]
+    async fn test_issue_maps_error_responses() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(serde_json::json!({
+                "rsp_cd": "IGW00105",
+                "rsp_msg": "유효하지 않은 AppSecret입니다.",
+            })))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(
+                ResponseTemplate::new(429)
+                    .insert_header("Retry-After", "5")
+                    .set_body_string(""),
+            )
+            .mount(&server)
+            .await;
+        let config = mock_config(&server, "error_map");
+        let client = Client::new();
+
+        assert!(matches!(
+            issue_access_token(&client, &config).await,
+            Err(AuthError::InvalidCredentials { rsp_cd, .. }) if rsp_cd == "IGW00105"
+        ));
+        assert!(matches!(
+            issue_access_token(&client, &config).await,
+            Err(AuthError::RateLimited { retry_after }) if retry_after.as_secs() == 5
+        ));
+
+        // 연결 불가 → Network
+        let offline = AppConfig {
+            environment: Environment::Custom {
+                rest_url: "http://127.0.0.1:1".to_string(),
+                ws_url: String::new(),
+            },
+            ..config.clone()
+        };
+        let err = issue_access_token(&client, &offline).await.unwrap_err();
+        assert!(matches!(err, AuthError::Network(_)));
+        assert!(err.is_retryable());
+        let _ = std::fs::remove_file(&config.token_cache_file);
+    }
+
+    fn guard_file(config: &AppConfig) -> String {
+        let path = format!(
+            "{}.{}.issued",
+            config.token_cache_file,
+            crate::auth::cache::fingerprint(&config.app_key)
+        );
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[tokio::test]
+    async fn test_cold_start_issues_token_then_approval_key() {
+        // main.rs 순서: 캐시 없이 토큰 발급 후 바로 접속키 발급
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .and(body_string_contains("appsecretkey="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "tok-cold",
+                "expires_in": 86400,
+                "scope": "oob",
+                "token_type": "Bearer",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .and(body_string_contains("\"secretkey\""))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"approval_key": "approval-1"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        let config = AppConfig {
+            token_issue_interval_secs: 3600,
+            ..mock_config(&server, "cold_start")
+        };
+        TokenCache::from_config(&config).clear().unwrap();
+        let guard_file = guard_file(&config);
+
+        let client = Client::new();
+        assert_eq!(
+            get_access_token(&client, &config).await.unwrap(),
+            "tok-cold"
+        );
+        // 간격이 지나지 않았으면 기다리지 않고 요청 없이 RateLimited
+        match crate::auth::ws_auth::get_ws_approval_key(&client, &config).await {
+            Err(AuthError::RateLimited { retry_after }) => {
+                assert!(retry_after.as_secs() > 3500)
+            }
+            other => panic!("RateLimited를 기대했지만 {:?}", other),
+        }
+
+        let config = AppConfig {
+            token_issue_interval_secs: 0,
+            ..config
+        };
+        assert_eq!(
+            crate::auth::ws_auth::get_ws_approval_key(&client, &config)
+                .await
+                .unwrap(),
+            "approval-1"
+        );
+
+        let _ = std::fs::remove_file(&guard_file);
+        let _ = std::fs::remove_file(&config.token_cache_file);
+    }
+
+    #[tokio::test]
+    async fn test_failed_issue_does_not_block_retry() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("일시 오류"))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "access_token": "tok-retry",
+                "expires_in": 86400,
+                "scope": "oob",
+                "token_type": "Bearer",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let config = AppConfig {
+            token_issue_interval_secs: 3600,
+            ..mock_config(&server, "retry_after_failure")
+        };
+        let guard_file = guard_file(&config);
+
+        let client = Client::new();
+        assert!(issue_access_token(&client, &config).await.is_err());
+        let token = issue_access_token(&client, &config).await.unwrap();
+        assert_eq!(token.access_token, "tok-retry");
+        // 성공한 발급은 다음 요청을 막음
+        assert!(matches!(
+            issue_access_token(&client, &config).await,
+            Err(AuthError::RateLimited { .. })
+        ));
+
+        let _ = std::fs::remove_file(&guard_file);
+        let _ = std::fs::remove_file(&config.token_cache_file);
+    }
 }
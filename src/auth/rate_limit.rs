@@ -0,0 +1,384 @@
+// 토큰 발급 요청 제한
+// LS는 토큰 발급(/oauth2/token) 요청 빈도를 제한하므로, 마지막 요청 시각을 상태 파일에 기록해
+// 여러 프로세스가 함께 최소 간격을 지키도록 합니다.
+// 시각은 요청 전에 예약해 두고, 발급에 실패하면 예약을 되돌려 바로 다시 시도할 수 있게 합니다.
+
+use crate::auth::cache::fingerprint;
+use crate::config::AppConfig;
+use log::info;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 현재 시각(UNIX epoch 초)을 돌려주는 시계. 테스트에서 시간을 조작할 수 있도록 분리합니다.
+pub trait Clock: Send + Sync {
+    fn now_secs(&self) -> u64;
+}
+
+/// 시스템 시계
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_secs(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+}
+
+/// 최소 간격 전에 발급을 요청한 경우
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimited {
+    pub retry_after: Duration,
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "토큰 발급 요청 제한: {}초 후 재시도하세요",
+            self.retry_after.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// 토큰 발급 요청 간격을 지키는 가드
+///
+/// 상태 파일에는 마지막 요청 시각(epoch 초)만 기록하며, 확인과 기록은 파일 lock 안에서 함께 처리합니다.
+pub struct IssuanceGuard<C: Clock = SystemClock> {
+    state_file: String,
+    min_interval: Duration,
+    clock: C,
+}
+
+impl IssuanceGuard<SystemClock> {
+    pub fn new(state_file: impl Into<String>, min_interval: Duration) -> Self {
+        Self::with_clock(state_file, min_interval, SystemClock)
+    }
+
+    /// app_key별 상태 파일(`<토큰캐시파일>.<app_key 지문>.issued`)과 설정된 최소 간격으로 만듭니다.
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(
+            format!(
+                "{}.{}.issued",
+                config.token_cache_file,
+                fingerprint(&config.app_key)
+            ),
+            Duration::from_secs(config.token_issue_interval_secs),
+        )
+    }
+}
+
+impl<C: Clock> IssuanceGuard<C> {
+    pub fn with_clock(state_file: impl Into<String>, min_interval: Duration, clock: C) -> Self {
+        Self {
+            state_file: state_file.into(),
+            min_interval,
+            clock,
+        }
+    }
+
+    /// 지금 발급을 요청할 수 있는지 확인만 합니다. (기록하지 않음)
+    pub fn can_request_token(&self) -> Result<(), RateLimited> {
+        let last = std::fs::read_to_string(&self.state_file)
+            .ok()
+            .and_then(|data| data.trim().parse::<u64>().ok());
+        self.check(last)
+    }
+
+    /// 요청 가능하면 현재 시각을 기록하고 통과시킵니다. (실패해도 되돌리지 않음)
+    pub fn acquire(&self) -> Result<(), RateLimited> {
+        self.reserve().map(Reservation::commit)
+    }
+
+    /// 요청 가능하면 현재 시각을 예약합니다.
+    ///
+    /// 동시에 여러 곳에서 발급하지 않도록 요청 전에 호출하고, 발급에 성공하면 `commit()` 합니다.
+    /// 커밋하지 않고 drop하면 이전 기록으로 되돌리므로 실패한 발급은 다음 시도를 막지 않습니다.
+    pub fn reserve(&self) -> Result<Reservation, RateLimited> {
+        let mut file = match open_locked(&self.state_file) {
+            Ok(file) => file,
+            Err(e) => {
+                // 상태 파일을 쓸 수 없으면 제한 없이 진행 (발급 자체를 막지 않음)
+                eprintln!("토큰 발급 상태 파일 열기 실패: {}", e);
+                return Ok(Reservation::empty());
+            }
+        };
+        let mut data = String::new();
+        let _ = file.read_to_string(&mut data);
+        let previous = data.trim().parse::<u64>().ok();
+        self.check(previous)?;
+        let now = self.clock.now_secs();
+        if let Err(e) = write_secs(&mut file, now) {
+            eprintln!("토큰 발급 시각 기록 실패: {}", e);
+            return Ok(Reservation::empty());
+        }
+        Ok(Reservation {
+            state_file: Some(self.state_file.clone()),
+            previous,
+            recorded: now,
+        })
+    }
+
+    /// 최소 간격이 지날 때까지 기다린 뒤 예약합니다.
+    ///
+    /// `TokenManager`가 거부된 토큰을 다시 발급할 때처럼, 호출자가 기다리기로 한 곳에서만 씁니다.
+    /// 그 밖의 발급은 `reserve()`로 `RateLimited`를 돌려줍니다.
+    pub async fn wait(&self) -> Reservation {
+        loop {
+            match self.reserve() {
+                Ok(reservation) => return reservation,
+                Err(RateLimited { retry_after }) => {
+                    info!("토큰 발급 최소 간격으로 {}초 대기", retry_after.as_secs());
+                    tokio::time::sleep(retry_after).await;
+                }
+            }
+        }
+    }
+
+    /// 마지막 요청 시각을 현재로 기록합니다.
+    pub fn update_last_request_time(&self) {
+        let result = open_locked(&self.state_file)
+            .and_then(|mut file| write_secs(&mut file, self.clock.now_secs()));
+        if let Err(e) = result {
+            eprintln!("토큰 발급 시각 기록 실패: {}", e);
+        }
+    }
+
+    fn check(&self, last: Option<u64>) -> Result<(), RateLimited> {
+        let Some(last) = last else {
+            return Ok(()); // 기록 없으면 요청 허용
+        };
+        // 시계가 뒤로 간 경우에도 최소 간격은 보장
+        let elapsed = Duration::from_secs(self.clock.now_secs().saturating_sub(last));
+        if elapsed >= self.min_interval {
+            Ok(())
+        } else {
+            Err(RateLimited {
+                retry_after: self.min_interval - elapsed,
+            })
+        }
+    }
+}
+
+/// `IssuanceGuard::reserve`로 예약한 발급 시각
+///
+/// `commit()`하지 않고 drop하면 상태 파일을 예약 전 값으로 되돌립니다.
+/// 그 사이 다른 프로세스가 새로 기록했다면 그 기록은 그대로 둡니다.
+#[must_use = "발급에 성공하면 commit()을 호출해야 합니다"]
+#[derive(Debug)]
+pub struct Reservation {
+    state_file: Option<String>,
+    previous: Option<u64>,
+    recorded: u64,
+}
+
+impl Reservation {
+    fn empty() -> Self {
+        Self {
+            state_file: None,
+            previous: None,
+            recorded: 0,
+        }
+    }
+
+    /// 발급에 성공했으므로 기록을 유지합니다.
+    pub fn commit(mut self) {
+        self.state_file = None;
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        let Some(state_file) = self.state_file.take() else {
+            return;
+        };
+        let result = open_locked(&state_file).and_then(|mut file| {
+            let mut data = String::new();
+            file.read_to_string(&mut data)?;
+            if data.trim().parse::<u64>().ok() != Some(self.recorded) {
+                return Ok(());
+            }
+            match self.previous {
+                Some(previous) => write_secs(&mut file, previous),
+                None => file.set_len(0),
+            }
+        });
+        if let Err(e) = result {
+            eprintln!("토큰 발급 시각 되돌리기 실패: {}", e);
+        }
+    }
+}
+
+/// 상태 파일을 열고 배타 lock을 겁니다. 반환된 File이 drop되면 해제됩니다.
+fn open_locked(state_file: &str) -> std::io::Result<File> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(state_file)?;
+    file.lock()?;
+    Ok(file)
+}
+
+fn write_secs(file: &mut File, secs: u64) -> std::io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(secs.to_string().as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_path;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    #[derive(Clone, Default)]
+    struct ManualClock(Arc<AtomicU64>);
+
+    impl ManualClock {
+        fn set(&self, secs: u64) {
+            self.0.store(secs, Ordering::SeqCst);
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now_secs(&self) -> u64 {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    #[test]
+    fn test_acquire_enforces_min_interval() {
+        let clock = ManualClock::default();
+        clock.set(1_000);
+        let path = temp_path("issue_interval");
+        let guard = IssuanceGuard::with_clock(&path, Duration::from_secs(60), clock.clone());
+
+        assert_eq!(guard.can_request_token(), Ok(()));
+        assert_eq!(guard.acquire(), Ok(()));
+
+        clock.set(1_045);
+        let err = guard.acquire().unwrap_err();
+        assert_eq!(err.retry_after, Duration::from_secs(15));
+        assert!(err.to_string().contains("15초"));
+        // 거절된 시도는 기록되지 않음
+        clock.set(1_060);
+        assert_eq!(guard.acquire(), Ok(()));
+        clock.set(1_061);
+        assert!(guard.can_request_token().is_err());
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_state_is_shared_through_file() {
+        let clock = ManualClock::default();
+        clock.set(5_000);
+        let path = temp_path("issue_shared");
+        // 같은 상태 파일을 쓰는 별도 가드(다른 프로세스 역할)
+        let a = IssuanceGuard::with_clock(&path, Duration::from_secs(10), clock.clone());
+        let b = IssuanceGuard::with_clock(&path, Duration::from_secs(10), clock.clone());
+
+        a.acquire().unwrap();
+        assert_eq!(
+            b.acquire(),
+            Err(RateLimited {
+                retry_after: Duration::from_secs(10)
+            })
+        );
+        clock.set(5_010);
+        assert_eq!(b.acquire(), Ok(()));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_clock_going_backwards_still_limited() {
+        let clock = ManualClock::default();
+        clock.set(2_000);
+        let path = temp_path("issue_backwards");
+        let guard = IssuanceGuard::with_clock(&path, Duration::from_secs(30), clock.clone());
+        guard.update_last_request_time();
+
+        clock.set(1_990);
+        assert_eq!(
+            guard.can_request_token().unwrap_err().retry_after,
+            Duration::from_secs(30)
+        );
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_concurrent_acquire_allows_only_one() {
+        let clock = ManualClock::default();
+        clock.set(9_000);
+        let path = temp_path("issue_concurrent");
+        let allowed: usize = (0..8)
+            .map(|_| {
+                let path = path.clone();
+                let clock = clock.clone();
+                std::thread::spawn(move || {
+                    IssuanceGuard::with_clock(&path, Duration::from_secs(60), clock)
+                        .acquire()
+                        .is_ok() as usize
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .sum();
+        assert_eq!(allowed, 1);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_uncommitted_reservation_is_rolled_back() {
+        let clock = ManualClock::default();
+        clock.set(3_000);
+        let path = temp_path("issue_rollback");
+        let guard = IssuanceGuard::with_clock(&path, Duration::from_secs(60), clock.clone());
+
+        // 기록이 없던 상태에서 실패하면 다시 바로 요청 가능
+        let reservation = guard.reserve().unwrap();
+        assert!(guard.can_request_token().is_err());
+        drop(reservation);
+        assert_eq!(guard.can_request_token(), Ok(()));
+
+        // 성공한 발급만 다음 요청을 막음
+        guard.reserve().unwrap().commit();
+        clock.set(3_070);
+        let reservation = guard.reserve().unwrap();
+        drop(reservation);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "3000");
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_interval_passes() {
+        let path = temp_path("issue_wait");
+        let guard = IssuanceGuard::new(&path, Duration::from_secs(1));
+        guard.reserve().unwrap().commit();
+        assert!(guard.reserve().is_err());
+
+        let started = std::time::Instant::now();
+        guard.wait().await.commit();
+        assert!(started.elapsed() >= Duration::from_millis(500));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_zero_interval_never_limits() {
+        let path = temp_path("issue_zero");
+        let guard = IssuanceGuard::new(&path, Duration::ZERO);
+        for _ in 0..3 {
+            assert_eq!(guard.acquire(), Ok(()));
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+}
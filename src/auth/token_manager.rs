@@ -0,0 +1,407 @@
+// 여러 태스크가 공유하는 접근 토큰 관리자
+// 메모리에 토큰을 보관하고 만료 전에 백그라운드에서 미리 갱신하며, 갱신 시 구독자에게 알립니다.
+
+use crate::auth::cache::{CachedToken, TokenCache};
+use crate::auth::error::{AuthError, mask};
+use crate::auth::oauth::{issue_access_token_when_allowed, revoke_access_token};
+use crate::config::AppConfig;
+use chrono::Utc;
+use log::{info, warn};
+use reqwest::Client;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::{Mutex, watch};
+use tokio::task::JoinHandle;
+
+/// 만료 몇 초 전에 갱신할지 기본값
+pub const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(10 * 60);
+/// 백그라운드 갱신 실패 시 재시도 간격
+pub const REFRESH_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Arc로 공유하는 토큰 관리자
+///
+/// - `token()`: 유효한 토큰을 돌려주고, 만료가 임박했으면 갱신합니다.
+/// - 동시에 여러 태스크가 갱신을 요청해도 실제 발급 요청은 한 번만 나갑니다.
+/// - `subscribe()`로 토큰 교체 알림을 받을 수 있습니다. (WebSocket 클라이언트 등)
+/// - `revoke()` 이후에는 새 토큰을 발급하지 않습니다.
+pub struct TokenManager {
+    config: AppConfig,
+    cache: TokenCache,
+    client: Client,
+    current: RwLock<Option<CachedToken>>,
+    refresh_lock: Mutex<()>,
+    rotation: watch::Sender<Option<String>>,
+    refresh_margin: Duration,
+    /// `revoke()`로 폐기된 뒤인지
+    closed: AtomicBool,
+}
+
+impl TokenManager {
+    /// 토큰 발급/폐기 요청은 `client`로 보냅니다.
+    pub fn new(client: Client, config: AppConfig) -> Arc<Self> {
+        Self::with_refresh_margin(client, config, DEFAULT_REFRESH_MARGIN)
+    }
+
+    /// 만료 `refresh_margin` 전부터 갱신 대상으로 봅니다.
+    pub fn with_refresh_margin(
+        client: Client,
+        config: AppConfig,
+        refresh_margin: Duration,
+    ) -> Arc<Self> {
+        // 캐시 파일에 유효한 토큰이 있으면 그대로 시작
+        let cache = TokenCache::from_config(&config);
+        let cached = cache.load().ok().filter(|t| t.expired_at > Utc::now());
+        let (rotation, _) = watch::channel(cached.as_ref().map(|t| t.access_token.clone()));
+        Arc::new(Self {
+            config,
+            cache,
+            client,
+            current: RwLock::new(cached),
+            refresh_lock: Mutex::new(()),
+            rotation,
+            refresh_margin,
+            closed: AtomicBool::new(false),
+        })
+    }
+
+    /// 유효한 접근 토큰. 없거나 만료가 임박했으면 갱신 후 돌려줍니다.
+    pub async fn token(&self) -> Result<String, AuthError> {
+        if let Some(token) = self.fresh_token() {
+            return Ok(token);
+        }
+        self.refresh_if_due().await
+    }
+
+    /// 현재 보관 중인 토큰 (만료 여부와 무관)
+    pub fn current(&self) -> Option<CachedToken> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// 토큰 교체 알림 구독. 값은 최신 access_token 입니다.
+    pub fn subscribe(&self) -> watch::Receiver<Option<String>> {
+        self.rotation.subscribe()
+    }
+
+    /// 서버가 토큰을 거부했을 때 호출합니다.
+    ///
+    /// 보관 중인 토큰이 `rejected`와 같을 때만 무효화하므로,
+    /// 여러 태스크가 같은 토큰으로 실패해도 갱신은 한 번만 일어납니다.
+    pub fn invalidate(&self, rejected: &str) {
+        let mut current = self.current.write().unwrap();
+        if current.as_ref().is_some_and(|t| t.access_token == rejected) {
+            *current = None;
+        }
+    }
+
+    /// 보관 중인 토큰을 폐기하고 캐시 파일을 지웁니다.
+    ///
+    /// 종료 시점에 호출하는 용도이며, 구독자에게는 `None`이 전달됩니다.
+    /// 폐기에 성공하면 백그라운드 갱신 태스크가 끝나고, 이후 `token()`은 `AuthError::Revoked`를 돌려줍니다.
+    pub async fn revoke(&self) -> Result<(), AuthError> {
+        let _guard = self.refresh_lock.lock().await;
+        let current = self.current.write().unwrap().take();
+        if let Some(token) = current
+            && token.expired_at > Utc::now()
+            && let Err(e) =
+                revoke_access_token(&self.client, &self.config, &token.access_token).await
+        {
+            // 폐기에 실패하면 토큰을 되돌려 계속 사용할 수 있게 둠
+            *self.current.write().unwrap() = Some(token);
+            return Err(e);
+        }
+        self.closed.store(true, Ordering::SeqCst);
+        self.rotation.send_replace(None);
+        self.cache
+            .clear()
+            .map_err(|e| AuthError::Cache(e.to_string()))?;
+        Ok(())
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// 갱신 기준(만료 - margin)을 지나지 않은 토큰
+    fn fresh_token(&self) -> Option<String> {
+        let margin = chrono::Duration::from_std(self.refresh_margin).unwrap_or_default();
+        self.current
+            .read()
+            .unwrap()
+            .as_ref()
+            .filter(|t| t.expired_at - margin > Utc::now())
+            .map(|t| t.access_token.clone())
+    }
+
+    /// 갱신이 필요하면 발급받습니다. 동시에 호출되면 먼저 들어온 한 태스크만 발급합니다.
+    async fn refresh_if_due(&self) -> Result<String, AuthError> {
+        let _guard = self.refresh_lock.lock().await;
+        if self.is_closed() {
+            return Err(AuthError::Revoked);
+        }
+        // 대기하는 동안 다른 태스크가 갱신했을 수 있음
+        if let Some(token) = self.fresh_token() {
+            return Ok(token);
+        }
+
+        // 거부된 토큰을 방금 발급받았을 수도 있으므로 RateLimited로 실패하지 않고 간격을 기다림
+        let issued = issue_access_token_when_allowed(&self.client, &self.config).await?;
+        if let Err(e) = self.cache.save(&issued) {
+            warn!("토큰 캐시 저장 실패: {}", e);
+        }
+        info!(
+            "토큰 갱신 완료: {} 만료시각={}",
+            mask(&issued.access_token),
+            issued.expired_at
+        );
+
+        let token = issued.access_token.clone();
+        *self.current.write().unwrap() = Some(issued);
+        self.rotation.send_replace(Some(token.clone()));
+        Ok(token)
+    }
+
+    /// 다음 갱신 시점까지 남은 시간
+    fn until_refresh(&self) -> Duration {
+        let margin = chrono::Duration::from_std(self.refresh_margin).unwrap_or_default();
+        self.current
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|t| {
+                (t.expired_at - margin - Utc::now())
+                    .to_std()
+                    .unwrap_or_default()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 만료 전에 토큰을 미리 갱신하는 백그라운드 태스크를 시작합니다.
+    ///
+    /// `revoke()`가 성공하면 태스크가 끝납니다.
+    pub fn spawn_refresh_task(self: &Arc<Self>) -> JoinHandle<()> {
+        let manager = Arc::clone(self);
+        let mut rotation = manager.subscribe();
+        tokio::spawn(async move {
+            loop {
+                rotation.borrow_and_update();
+                tokio::select! {
+                    _ = tokio::time::sleep(manager.until_refresh()) => {}
+                    // 토큰이 교체되거나 폐기되면 갱신 시점을 다시 계산
+                    _ = rotation.changed() => {
+                        if manager.is_closed() {
+                            break;
+                        }
+                        continue;
+                    }
+                }
+                let failed = match manager.refresh_if_due().await {
+                    Ok(_) => false,
+                    Err(AuthError::Revoked) => break,
+                    Err(e) => {
+                        warn!(
+                            "토큰 백그라운드 갱신 실패: {}. {}초 후 재시도",
+                            e,
+                            REFRESH_RETRY_INTERVAL.as_secs()
+                        );
+                        true
+                    }
+                };
+                if failed {
+                    tokio::time::sleep(REFRESH_RETRY_INTERVAL).await;
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{mock_config, temp_path};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_config(server: &MockServer, cache_file: &str) -> AppConfig {
+        AppConfig {
+            app_key: "test-key".to_string(),
+            app_secret: "test-secret".into(),
+            token_cache_file: cache_file.to_string(),
+            token_issue_interval_secs: 0,
+            ..mock_config(server)
+        }
+    }
+
+    fn token_response(token: &str, expires_in: u64) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "access_token": token,
+            "expires_in": expires_in,
+            "scope": "oob",
+            "token_type": "Bearer",
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_callers_share_one_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(token_response("tok-1", 86400).set_delay(Duration::from_millis(50)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache = temp_path("token_manager_concurrent.json");
+        let manager = TokenManager::new(Client::new(), test_config(&server, &cache));
+        let mut rotation = manager.subscribe();
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let manager = Arc::clone(&manager);
+                tokio::spawn(async move { manager.token().await.unwrap() })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await.unwrap(), "tok-1");
+        }
+
+        assert!(rotation.has_changed().unwrap());
+        assert_eq!(rotation.borrow_and_update().as_deref(), Some("tok-1"));
+        // 캐시 파일에도 저장됨
+        assert_eq!(manager.cache.load().unwrap().access_token, "tok-1");
+        let _ = std::fs::remove_file(&cache);
+    }
+
+    #[tokio::test]
+    async fn test_background_refresh_before_expiry() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(token_response("tok-1", 1))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(token_response("tok-2", 86400))
+            .mount(&server)
+            .await;
+
+        let cache = temp_path("token_manager_background.json");
+        let manager = TokenManager::with_refresh_margin(
+            Client::new(),
+            test_config(&server, &cache),
+            Duration::from_millis(500),
+        );
+        // 만료 1초짜리 토큰은 margin(0.5초) 이후 곧바로 갱신 대상
+        let first = manager.refresh_if_due().await.unwrap();
+        assert_eq!(first, "tok-1");
+
+        let mut rotation = manager.subscribe();
+        rotation.borrow_and_update();
+        let task = manager.spawn_refresh_task();
+        tokio::time::timeout(Duration::from_secs(3), rotation.changed())
+            .await
+            .expect("만료 전에 갱신되어야 함")
+            .unwrap();
+        assert_eq!(rotation.borrow().as_deref(), Some("tok-2"));
+        assert_eq!(manager.token().await.unwrap(), "tok-2");
+
+        task.abort();
+        let _ = std::fs::remove_file(&cache);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_clears_token_and_cache() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(token_response("tok-1", 86400))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/revoke"))
+            .and(wiremock::matchers::body_string_contains("token=tok-1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"rsp_cd": "00000", "rsp_msg": "ok"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache = temp_path("token_manager_revoke.json");
+        let manager = TokenManager::new(Client::new(), test_config(&server, &cache));
+        manager.token().await.unwrap();
+        let rotation = manager.subscribe();
+
+        manager.revoke().await.unwrap();
+        assert!(manager.current().is_none());
+        assert!(rotation.borrow().is_none());
+        assert!(manager.cache.load().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_stops_refresh_task() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(token_response("tok-1", 86400))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/revoke"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"rsp_cd": "00000", "rsp_msg": "ok"})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache = temp_path("token_manager_revoke_task.json");
+        let manager = TokenManager::new(Client::new(), test_config(&server, &cache));
+        manager.token().await.unwrap();
+        let task = manager.spawn_refresh_task();
+
+        manager.revoke().await.unwrap();
+        // 폐기 후 태스크는 재발급 없이 끝나야 함
+        tokio::time::timeout(Duration::from_secs(3), task)
+            .await
+            .expect("폐기 후 갱신 태스크가 끝나야 함")
+            .unwrap();
+        assert!(matches!(manager.token().await, Err(AuthError::Revoked)));
+        assert!(manager.current().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_forces_single_refresh() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/oauth2/token"))
+            .respond_with(token_response("tok-new", 86400))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache = temp_path("token_manager_invalidate.json");
+        let config = test_config(&server, &cache);
+        TokenCache::from_config(&config)
+            .save(&CachedToken {
+                access_token: "tok-old".to_string(),
+                expired_at: Utc::now() + chrono::Duration::days(1),
+            })
+            .unwrap();
+        let manager = TokenManager::new(Client::new(), config);
+        assert_eq!(manager.token().await.unwrap(), "tok-old");
+
+        manager.invalidate("tok-old");
+        manager.invalidate("tok-old");
+        assert_eq!(manager.token().await.unwrap(), "tok-new");
+        // 이미 교체된 토큰에 대한 무효화는 무시
+        manager.invalidate("tok-old");
+        assert_eq!(manager.token().await.unwrap(), "tok-new");
+        let _ = std::fs::remove_file(&cache);
+    }
+}
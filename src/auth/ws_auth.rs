@@ -1,25 +1,30 @@
+use crate::auth::error::{AuthError, mask};
+use crate::auth::oauth::read_response;
+use crate::auth::rate_limit::IssuanceGuard;
 use crate::config::AppConfig;
 
-use log::{debug, error, info};
+use log::info;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use std::fs;
-use std::time::{SystemTime, UNIX_EPOCH};
+use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
 pub struct ApprovalKeyResponse {
     pub approval_key: String,
 }
 
-pub async fn get_ws_approval_key(config: &AppConfig) -> Result<String, Box<dyn std::error::Error>> {
-    let client = Client::new();
+/// WebSocket 접속키 발급. 토큰 발급과 같은 요청 제한을 받습니다.
+///
+/// 최소 간격이 지나지 않았으면 요청하지 않고 `AuthError::RateLimited`로 남은 시간을 알려줍니다.
+pub async fn get_ws_approval_key(client: &Client, config: &AppConfig) -> Result<String, AuthError> {
+    let reservation = IssuanceGuard::from_config(config).reserve()?;
+
     let body = serde_json::json!({
         "grant_type": "client_credentials",
         "appkey": &config.app_key,
-        "secretkey": &config.app_secret,
+        "secretkey": config.app_secret.expose(),
     });
 
-    let approval_url = format!("{}/oauth2/token", config.token_url);
+    let approval_url = format!("{}/oauth2/token", config.rest_base_url());
 
     let resp = client
         .post(&approval_url)
@@ -28,43 +33,16 @@ pub async fn get_ws_approval_key(config: &AppConfig) -> Result<String, Box<dyn s
         .send()
         .await?;
 
-    let text = resp.text().await?;
-    debug!("KIS ApprovalKey 응답 원문: {}", text);
+    let text = read_response(resp, "ApprovalKey 발급").await?;
 
-    let key_result: Result<ApprovalKeyResponse, _> = serde_json::from_str(&text);
-    let key = match key_result {
-        Ok(key) => key,
-        Err(_) => {
-            error!("KIS ApprovalKey 발급 실패 응답: {}", text);
-            return Err(format!("KIS ApprovalKey 발급 실패: {}", text).into());
-        }
-    };
+    let key: ApprovalKeyResponse = serde_json::from_str(&text)
+        .map_err(|e| AuthError::InvalidResponse(format!("ApprovalKey 파싱 실패: {}", e)))?;
 
-    info!("신규 ApprovalKey 발급: {}", key.approval_key);
+    reservation.commit();
+    info!("신규 ApprovalKey 발급: {}", mask(&key.approval_key));
     Ok(key.approval_key)
 }
 
-fn can_request_token(last_request_file: &str, min_interval_secs: u64) -> bool {
-    if let Ok(data) = fs::read_to_string(last_request_file) {
-        if let Ok(last) = data.parse::<u64>() {
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            return now - last >= min_interval_secs;
-        }
-    }
-    true // 파일 없으면 요청 허용
-}
-
-fn update_last_request_time(last_request_file: &str) {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    let _ = fs::write(last_request_file, now.to_string());
-}
-
 // #[cfg(test)]
 // mod tests {
 //     use super::*;
@@ -1,24 +1,1769 @@
+use crate::environment::Environment;
+use crate::http::build_client;
+use crate::http::retry::RetryPolicy;
+use crate::secret::{Secret, SecretError, SecretRef};
+use crate::types::tr_code::{TrCode, TrKeyKind};
+use crate::types::tr_key::{TrKey, TrKeyError};
+use crate::websocket::client::ClientConfig;
 use dotenv::dotenv;
+use log::warn;
+use reqwest::Client;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use toml::{Table, Value};
+
+/// 토큰 발급 요청 최소 간격 기본값(초)
+pub const DEFAULT_TOKEN_ISSUE_INTERVAL_SECS: u64 = 60;
+/// 토큰 캐시 파일 기본 경로
+pub const DEFAULT_TOKEN_CACHE_FILE: &str = "xing_token_cache.json";
+/// 따로 지정하지 않았을 때 읽는 설정 파일 (없으면 건너뜀)
+pub const DEFAULT_CONFIG_FILE: &str = "xing.toml";
+/// 설정 파일 경로를 지정하는 환경변수
+pub const CONFIG_FILE_ENV: &str = "XING_CONFIG";
+/// REST 요청의 User-Agent 기본값
+pub const DEFAULT_USER_AGENT: &str = concat!("xing_trading_rust/", env!("CARGO_PKG_VERSION"));
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
     pub app_key: String,
-    pub app_secret: String,
-    pub token_url: String,
+    /// 설정에는 `env:`/`file:`/`cmd:` 출처를 적을 수 있습니다. (`SecretRef` 참고)
+    pub app_secret: Secret,
+    /// 계좌번호 (숫자만)
+    pub account_no: Option<String>,
+    /// 접속 환경. REST/WebSocket 주소는 여기서 결정됩니다.
+    pub environment: Environment,
+    pub token_cache_file: String,
+    /// 토큰 캐시 암호화 키 (base64 인코딩된 32바이트). 없으면 평문으로 저장
+    pub token_cache_key: Option<Secret>,
+    /// 토큰 발급 요청 최소 간격(초). 0이면 제한하지 않음
+    pub token_issue_interval_secs: u64,
+    pub ws: WsSettings,
+    pub zmq: ZmqSettings,
+    pub recording: RecordingSettings,
+    pub retry: RetrySettings,
+    pub http: HttpSettings,
+    /// 시작 시 등록할 실시간 구독
+    pub subscriptions: Vec<SubscriptionSetting>,
+    /// `[accounts.<이름>]`로 정의한 계좌 목록 (이름순)
+    pub accounts: Vec<AccountConfig>,
+    /// 최상위 인증 정보로 쓰는 계좌 이름
+    pub default_account: Option<String>,
+    /// TR별 초당 호출 수. LS 기본값(`LS_RATE_LIMITS`)을 덮어쓰며 0이면 제한하지 않음
+    pub rate_limits: BTreeMap<String, u32>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            app_key: String::new(),
+            app_secret: Secret::default(),
+            account_no: None,
+            environment: Environment::default(),
+            token_cache_file: DEFAULT_TOKEN_CACHE_FILE.to_string(),
+            token_cache_key: None,
+            token_issue_interval_secs: DEFAULT_TOKEN_ISSUE_INTERVAL_SECS,
+            ws: WsSettings::default(),
+            zmq: ZmqSettings::default(),
+            recording: RecordingSettings::default(),
+            retry: RetrySettings::default(),
+            http: HttpSettings::default(),
+            subscriptions: Vec::new(),
+            accounts: Vec::new(),
+            default_account: None,
+            rate_limits: BTreeMap::new(),
+        }
+    }
+}
+
+/// `[ws]` 재연결/ping 설정
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct WsSettings {
+    pub reconnect_interval_secs: u64,
+    pub max_reconnect_interval_secs: u64,
+    pub max_reconnect_attempts: usize,
+    pub max_fatal_failures: usize,
+    pub ping_interval_secs: u64,
+}
+
+impl Default for WsSettings {
+    fn default() -> Self {
+        Self {
+            reconnect_interval_secs: 5,
+            max_reconnect_interval_secs: 60,
+            max_reconnect_attempts: 10,
+            max_fatal_failures: 3,
+            ping_interval_secs: 60,
+        }
+    }
+}
+
+/// `[zmq]` publish 주소
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ZmqSettings {
+    pub orderbook_endpoint: String,
+    pub trade_endpoint: String,
+}
+
+impl Default for ZmqSettings {
+    fn default() -> Self {
+        Self {
+            orderbook_endpoint: "tcp://0.0.0.0:5557".to_string(),
+            trade_endpoint: "tcp://0.0.0.0:5558".to_string(),
+        }
+    }
+}
+
+/// `[recording]` 수신 데이터 출력/저장 설정
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RecordingSettings {
+    pub print_console: bool,
+    pub save_to_file: bool,
+    pub file_path: Option<String>,
+}
+
+impl Default for RecordingSettings {
+    fn default() -> Self {
+        Self {
+            print_console: true,
+            save_to_file: false,
+            file_path: None,
+        }
+    }
+}
+
+/// `[retry]` 조회 TR 재시도 설정
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RetrySettings {
+    /// 첫 시도 뒤 추가로 보내는 최대 횟수. 0이면 재시도하지 않음
+    pub max_retries: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// 대기시간을 무작위로 줄이는 비율 (0.0 ~ 1.0)
+    pub jitter: f64,
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        let policy = RetryPolicy::default();
+        Self {
+            max_retries: policy.max_retries,
+            initial_backoff_ms: policy.initial_backoff.as_millis() as u64,
+            max_backoff_ms: policy.max_backoff.as_millis() as u64,
+            jitter: policy.jitter,
+        }
+    }
+}
+
+/// `[http]` REST 호출(토큰 발급/TR)에 쓰는 HTTP 클라이언트 설정
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct HttpSettings {
+    pub connect_timeout_ms: u64,
+    /// 요청 하나의 전체 제한 시간. 0이면 제한하지 않음
+    pub timeout_ms: u64,
+    /// 호스트별로 열어 둘 유휴 연결 수
+    pub pool_max_idle_per_host: usize,
+    pub pool_idle_timeout_secs: u64,
+    /// TCP keep-alive 간격. 0이면 끔
+    pub tcp_keepalive_secs: u64,
+    pub user_agent: String,
+    /// 프록시 주소 (예: `http://proxy.local:3128`). 없으면 시스템 프록시 환경변수를 따름
+    pub proxy: Option<String>,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout_ms: 5_000,
+            timeout_ms: 10_000,
+            pool_max_idle_per_host: 8,
+            pool_idle_timeout_secs: 90,
+            tcp_keepalive_secs: 60,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            proxy: None,
+        }
+    }
+}
+
+/// `[[subscriptions]]` 항목
+///
+/// TOML에서는 `{ tr_cd = "UH1", key = "005930" }`, 환경변수/CLI에서는 `UH1:005930,US3:000660` 형식입니다.
+/// 계좌 단위 TR은 key를 비워 둡니다.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SubscriptionSetting {
+    pub tr_cd: TrCode,
+    #[serde(default)]
+    pub key: String,
+}
+
+impl SubscriptionSetting {
+    /// TR 형식에 맞는 tr_key
+    pub fn tr_key(&self) -> Result<TrKey, TrKeyError> {
+        match self.tr_cd.key_kind() {
+            TrKeyKind::Symbol => TrKey::for_symbol(self.tr_cd, &self.key),
+            TrKeyKind::Sector => TrKey::for_sector(self.tr_cd, &self.key),
+            TrKeyKind::Account => TrKey::account(self.tr_cd),
+            TrKeyKind::Other => TrKey::raw(self.tr_cd, &self.key),
+        }
+    }
+}
+
+/// `[accounts.<이름>]` 계좌별 설정
+///
+/// ```toml
+/// default_account = "main"
+///
+/// [accounts.main]
+/// app_key = "..."
+/// app_secret = "..."
+/// account_no = "555-01234-501"
+///
+/// [accounts.paper]
+/// app_key = "..."
+/// app_secret = "..."
+/// environment = "demo"
+/// ```
+///
+/// `environment`/`rest_url`/`ws_url`/`token_cache_file`/`token_cache_key`가 없으면 최상위 값을 따릅니다.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct AccountConfig {
+    pub name: String,
+    pub app_key: String,
+    pub app_secret: Secret,
+    pub account_no: Option<String>,
+    pub environment: Environment,
     pub token_cache_file: String,
+    pub token_cache_key: Option<Secret>,
+}
+
+impl AccountConfig {
+    /// `base`에 이 계좌의 인증 정보/접속 환경/토큰 캐시 설정을 덮어쓴 설정
+    fn apply(&self, base: &AppConfig) -> AppConfig {
+        AppConfig {
+            app_key: self.app_key.clone(),
+            app_secret: self.app_secret.clone(),
+            account_no: self.account_no.clone(),
+            environment: self.environment.clone(),
+            token_cache_file: self.token_cache_file.clone(),
+            token_cache_key: self.token_cache_key.clone(),
+            ..base.clone()
+        }
+    }
 }
 
 impl AppConfig {
+    /// 설정 파일, 환경변수(.env 포함), 프로세스 CLI 인자 순으로 설정을 읽습니다.
+    ///
+    /// 설정 파일은 `--config`, `XING_CONFIG` 순으로 찾고 둘 다 없으면 `xing.toml`이 있을 때만 읽습니다.
+    pub fn load() -> Result<Self, ConfigError> {
+        dotenv().ok();
+        ConfigLoader::new()
+            .env_vars(env::vars())
+            .args(env::args().skip(1))
+            .load()
+    }
+
+    /// 설정 파일과 환경변수로 설정을 읽고, 문제가 있으면 panic 합니다.
     pub fn from_env() -> Self {
         dotenv().ok();
-        Self {
-            app_key: env::var("XING_APP_KEY").expect("XING_APP_KEY 없음"),
-            app_secret: env::var("XING_SECRET_KEY").expect("XING_APP_SECRET 없음"),
-            token_url: env::var("XING_TOKEN_DOMAI").expect("XING_TOKEN_DOMAI 없음"),
-            token_cache_file: env::var("KIS_TOKEN_CACHE_FILE")
-                .unwrap_or("kis_token_cache.json".to_string()),
+        ConfigLoader::new()
+            .env_vars(env::vars())
+            .load()
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// REST API base URL
+    pub fn rest_base_url(&self) -> &str {
+        self.environment.rest_base_url()
+    }
+
+    /// WebSocket 접속 URL
+    pub fn ws_url(&self) -> String {
+        self.environment.ws_url()
+    }
+
+    /// 이름으로 찾은 계좌의 인증 정보/접속 환경을 적용한 설정
+    ///
+    /// 반환값의 `app_key` 등은 그 계좌 값이므로 토큰 발급/캐시도 계좌별로 나뉩니다.
+    pub fn account(&self, name: &str) -> Option<AppConfig> {
+        self.accounts
+            .iter()
+            .find(|account| account.name == name)
+            .map(|account| account.apply(self))
+    }
+
+    /// 접속 환경과 `[ws]` 설정을 반영한 WebSocket 클라이언트 설정
+    pub fn client_config(&self) -> ClientConfig {
+        ClientConfig {
+            reconnect_interval: Duration::from_secs(self.ws.reconnect_interval_secs),
+            max_reconnect_interval: Duration::from_secs(self.ws.max_reconnect_interval_secs),
+            max_reconnect_attempts: self.ws.max_reconnect_attempts,
+            max_fatal_failures: self.ws.max_fatal_failures,
+            ping_interval: Duration::from_secs(self.ws.ping_interval_secs),
+            ..ClientConfig::for_environment(&self.environment)
+        }
+    }
+
+    /// `[http]` 설정으로 만든 HTTP 클라이언트
+    ///
+    /// 연결 풀을 같이 쓰도록 한 번 만들어 토큰 발급, TR 호출 등에 넘겨 쓰세요. (`Client`는 복제해도 풀을 공유합니다)
+    pub fn http_client(&self) -> Result<Client, reqwest::Error> {
+        build_client(&self.http)
+    }
+
+    /// `[retry]` 설정을 반영한 조회 TR 재시도 정책
+    pub fn retry_policy(&self) -> RetryPolicy {
+        RetryPolicy {
+            max_retries: self.retry.max_retries,
+            initial_backoff: Duration::from_millis(self.retry.initial_backoff_ms),
+            max_backoff: Duration::from_millis(self.retry.max_backoff_ms),
+            jitter: self.retry.jitter,
+            ..RetryPolicy::default()
+        }
+    }
+}
+
+/// 설정 키와 환경변수 이름. 첫 번째가 현재 이름이고 나머지는 예전 이름입니다.
+struct KeySpec {
+    key: &'static str,
+    env: &'static [&'static str],
+}
+
+const KEYS: &[KeySpec] = &[
+    KeySpec {
+        key: "app_key",
+        env: &["XING_APP_KEY"],
+    },
+    KeySpec {
+        key: "app_secret",
+        env: &["XING_APP_SECRET", "XING_SECRET_KEY"],
+    },
+    KeySpec {
+        key: "account_no",
+        env: &["XING_ACCOUNT_NO"],
+    },
+    KeySpec {
+        key: "environment",
+        env: &["XING_ENV"],
+    },
+    KeySpec {
+        key: "rest_url",
+        env: &["XING_REST_URL", "XING_TOKEN_DOMAI"],
+    },
+    KeySpec {
+        key: "ws_url",
+        env: &["XING_WS_URL"],
+    },
+    KeySpec {
+        key: "token_cache_file",
+        env: &["XING_TOKEN_CACHE_FILE", "KIS_TOKEN_CACHE_FILE"],
+    },
+    KeySpec {
+        key: "token_cache_key",
+        env: &["XING_TOKEN_CACHE_KEY"],
+    },
+    KeySpec {
+        key: "token_issue_interval_secs",
+        env: &["XING_TOKEN_ISSUE_INTERVAL_SECS"],
+    },
+    KeySpec {
+        key: "ws.reconnect_interval_secs",
+        env: &["XING_WS_RECONNECT_INTERVAL_SECS"],
+    },
+    KeySpec {
+        key: "ws.max_reconnect_interval_secs",
+        env: &["XING_WS_MAX_RECONNECT_INTERVAL_SECS"],
+    },
+    KeySpec {
+        key: "ws.max_reconnect_attempts",
+        env: &["XING_WS_MAX_RECONNECT_ATTEMPTS"],
+    },
+    KeySpec {
+        key: "ws.max_fatal_failures",
+        env: &["XING_WS_MAX_FATAL_FAILURES"],
+    },
+    KeySpec {
+        key: "ws.ping_interval_secs",
+        env: &["XING_WS_PING_INTERVAL_SECS"],
+    },
+    KeySpec {
+        key: "zmq.orderbook_endpoint",
+        env: &["XING_ZMQ_ORDERBOOK_ENDPOINT"],
+    },
+    KeySpec {
+        key: "zmq.trade_endpoint",
+        env: &["XING_ZMQ_TRADE_ENDPOINT"],
+    },
+    KeySpec {
+        key: "recording.print_console",
+        env: &["XING_RECORDING_PRINT_CONSOLE"],
+    },
+    KeySpec {
+        key: "recording.save_to_file",
+        env: &["XING_RECORDING_SAVE_TO_FILE"],
+    },
+    KeySpec {
+        key: "recording.file_path",
+        env: &["XING_RECORDING_FILE_PATH"],
+    },
+    KeySpec {
+        key: "retry.max_retries",
+        env: &["XING_RETRY_MAX_RETRIES"],
+    },
+    KeySpec {
+        key: "retry.initial_backoff_ms",
+        env: &["XING_RETRY_INITIAL_BACKOFF_MS"],
+    },
+    KeySpec {
+        key: "retry.max_backoff_ms",
+        env: &["XING_RETRY_MAX_BACKOFF_MS"],
+    },
+    KeySpec {
+        key: "retry.jitter",
+        env: &["XING_RETRY_JITTER"],
+    },
+    KeySpec {
+        key: "http.connect_timeout_ms",
+        env: &["XING_HTTP_CONNECT_TIMEOUT_MS"],
+    },
+    KeySpec {
+        key: "http.timeout_ms",
+        env: &["XING_HTTP_TIMEOUT_MS"],
+    },
+    KeySpec {
+        key: "http.pool_max_idle_per_host",
+        env: &["XING_HTTP_POOL_MAX_IDLE_PER_HOST"],
+    },
+    KeySpec {
+        key: "http.pool_idle_timeout_secs",
+        env: &["XING_HTTP_POOL_IDLE_TIMEOUT_SECS"],
+    },
+    KeySpec {
+        key: "http.tcp_keepalive_secs",
+        env: &["XING_HTTP_TCP_KEEPALIVE_SECS"],
+    },
+    KeySpec {
+        key: "http.user_agent",
+        env: &["XING_HTTP_USER_AGENT"],
+    },
+    KeySpec {
+        key: "http.proxy",
+        env: &["XING_HTTP_PROXY"],
+    },
+    KeySpec {
+        key: "subscriptions",
+        env: &["XING_SUBSCRIPTIONS"],
+    },
+    KeySpec {
+        key: "default_account",
+        env: &["XING_DEFAULT_ACCOUNT"],
+    },
+    KeySpec {
+        key: "rate_limits",
+        env: &["XING_RATE_LIMITS"],
+    },
+];
+
+/// `[accounts.<이름>]` 테이블에 쓸 수 있는 키
+const ACCOUNT_FIELDS: &[&str] = &[
+    "app_key",
+    "app_secret",
+    "account_no",
+    "environment",
+    "rest_url",
+    "ws_url",
+    "token_cache_file",
+    "token_cache_key",
+];
+
+fn key_spec(key: &str) -> Option<&'static KeySpec> {
+    KEYS.iter().find(|spec| spec.key == key)
+}
+
+/// `accounts.<이름>.<키>` 형식의 계좌별 설정 키인지 여부
+fn is_account_key(key: &str) -> bool {
+    key.strip_prefix("accounts.")
+        .and_then(|rest| rest.split_once('.'))
+        .is_some_and(|(name, field)| is_valid_account_name(name) && ACCOUNT_FIELDS.contains(&field))
+}
+
+fn is_valid_account_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// 설정 문제 하나
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigIssue {
+    /// 필수 값 없음
+    Missing { key: String },
+    /// 값 형식 오류
+    Invalid {
+        key: String,
+        value: String,
+        reason: String,
+    },
+    /// 알 수 없는 CLI 인자
+    UnknownArg(String),
+    /// 설정 파일의 알 수 없는 키 (오타 등)
+    UnknownKey(String),
+    /// 설정 파일을 읽거나 파싱하지 못함
+    File { path: String, reason: String },
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigIssue::Missing { key } => match key_spec(key) {
+                Some(spec) => write!(f, "{} 없음 (환경변수 {} 또는 --{})", key, spec.env[0], key),
+                None => write!(f, "{} 없음", key),
+            },
+            ConfigIssue::Invalid { key, value, reason } => {
+                write!(f, "{} 값 오류 {:?}: {}", key, value, reason)
+            }
+            ConfigIssue::UnknownArg(arg) => write!(f, "알 수 없는 인자: {}", arg),
+            ConfigIssue::UnknownKey(key) => write!(f, "알 수 없는 설정 키: {}", key),
+            ConfigIssue::File { path, reason } => write!(f, "설정 파일 {}: {}", path, reason),
+        }
+    }
+}
+
+/// 설정 로딩 실패. 발견한 문제를 모두 담습니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl ConfigError {
+    /// 값이 없는 키 목록
+    pub fn missing_keys(&self) -> Vec<&str> {
+        self.issues
+            .iter()
+            .filter_map(|issue| match issue {
+                ConfigIssue::Missing { key } => Some(key.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// 값 형식이 잘못된 키 목록
+    pub fn invalid_keys(&self) -> Vec<&str> {
+        self.issues
+            .iter()
+            .filter_map(|issue| match issue {
+                ConfigIssue::Invalid { key, .. } => Some(key.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "설정 오류 {}건", self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n  - {}", issue)?;
         }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// 설정 파일 → 환경변수 → CLI 인자 순으로 값을 덮어써 `AppConfig`를 만듭니다.
+///
+/// CLI 인자는 `--ws.ping_interval_secs=30`, `--app-key KEY`처럼 설정 키 이름을 그대로 쓰고
+/// (`-`는 `_`로 취급), `--config PATH`로 설정 파일을 지정합니다.
+#[derive(Debug, Default)]
+pub struct ConfigLoader {
+    file: Option<PathBuf>,
+    env: Vec<(String, String)>,
+    args: Vec<String>,
+}
+
+impl ConfigLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 읽을 설정 파일. 지정한 파일이 없으면 에러입니다.
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    pub fn env_vars(mut self, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.env = vars.into_iter().collect();
+        self
+    }
+
+    pub fn args(mut self, args: impl IntoIterator<Item = String>) -> Self {
+        self.args = args.into_iter().collect();
+        self
+    }
+
+    pub fn load(self) -> Result<AppConfig, ConfigError> {
+        let mut issues = Vec::new();
+        let (overrides, config_arg) = parse_args(&self.args, &mut issues);
+
+        let path = config_arg
+            .or_else(|| self.file.clone())
+            .or_else(|| self.env_var(CONFIG_FILE_ENV).map(PathBuf::from));
+        let mut table = match path {
+            Some(path) => read_file(&path, &mut issues),
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => {
+                read_file(Path::new(DEFAULT_CONFIG_FILE), &mut issues)
+            }
+            None => Table::new(),
+        };
+
+        for spec in KEYS {
+            let found = spec
+                .env
+                .iter()
+                .find_map(|name| self.env_var(name).map(|value| (*name, value)));
+            if let Some((name, value)) = found {
+                if name != spec.env[0] {
+                    warn!("{}는 예전 이름입니다. {}를 사용하세요.", name, spec.env[0]);
+                }
+                set_path(&mut table, spec.key, Value::String(value));
+            }
+        }
+
+        for (key, value) in overrides {
+            set_path(&mut table, &key, Value::String(value));
+        }
+        unknown_keys(&table, &mut issues);
+
+        let config = Reader {
+            table: &table,
+            issues: &mut issues,
+            loader: &self,
+        }
+        .app_config();
+        if issues.is_empty() {
+            Ok(config)
+        } else {
+            Err(ConfigError { issues })
+        }
+    }
+
+    fn env_var(&self, name: &str) -> Option<String> {
+        self.env
+            .iter()
+            .rev()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.clone())
+            .filter(|v| !v.is_empty())
+    }
+}
+
+/// CLI 인자를 (설정 키, 값) 목록과 `--config` 경로로 나눕니다.
+fn parse_args(
+    args: &[String],
+    issues: &mut Vec<ConfigIssue>,
+) -> (Vec<(String, String)>, Option<PathBuf>) {
+    let mut overrides = Vec::new();
+    let mut config_file = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let Some(option) = arg.strip_prefix("--") else {
+            issues.push(ConfigIssue::UnknownArg(arg.clone()));
+            continue;
+        };
+        let (name, inline) = match option.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (option, None),
+        };
+        // 계좌 이름에는 `-`를 쓸 수 있으므로 마지막 키 이름만 `_`로 바꿉니다.
+        let key = match name.rsplit_once('.') {
+            Some((section, field)) => format!("{}.{}", section, field.replace('-', "_")),
+            None => name.replace('-', "_"),
+        };
+        if key != "config" && key_spec(&key).is_none() && !is_account_key(&key) {
+            issues.push(ConfigIssue::UnknownArg(arg.clone()));
+            continue;
+        }
+        let Some(value) = inline.or_else(|| iter.next().cloned()) else {
+            issues.push(ConfigIssue::Invalid {
+                key,
+                value: String::new(),
+                reason: "값이 필요합니다".to_string(),
+            });
+            continue;
+        };
+        if key == "config" {
+            config_file = Some(PathBuf::from(value));
+        } else {
+            overrides.push((key, value));
+        }
+    }
+    (overrides, config_file)
+}
+
+/// `KEYS`와 `ACCOUNT_FIELDS`에 없는 키를 모두 `UnknownKey`로 기록합니다.
+///
+/// 환경변수와 CLI 인자는 이미 걸러졌으므로 사실상 설정 파일의 오타를 찾습니다.
+/// `subscriptions`/`rate_limits`처럼 테이블을 값으로 받는 키의 안쪽은 `Reader`가 검사합니다.
+fn unknown_keys(table: &Table, issues: &mut Vec<ConfigIssue>) {
+    for (key, value) in table {
+        if key_spec(key).is_some() {
+            continue;
+        }
+        match value {
+            Value::Table(accounts) if key == "accounts" => {
+                for (name, account) in accounts {
+                    // 계좌 이름 오류는 Reader가 Invalid로 기록
+                    let Value::Table(fields) = account else {
+                        continue;
+                    };
+                    for field in fields.keys() {
+                        if !ACCOUNT_FIELDS.contains(&field.as_str()) {
+                            issues.push(ConfigIssue::UnknownKey(format!(
+                                "accounts.{}.{}",
+                                name, field
+                            )));
+                        }
+                    }
+                }
+            }
+            Value::Table(section) if is_section(key) => {
+                for field in section.keys() {
+                    let full = format!("{}.{}", key, field);
+                    if key_spec(&full).is_none() {
+                        issues.push(ConfigIssue::UnknownKey(full));
+                    }
+                }
+            }
+            _ => issues.push(ConfigIssue::UnknownKey(key.clone())),
+        }
+    }
+}
+
+/// `[ws]`처럼 하위 키를 갖는 섹션 이름인지 여부
+fn is_section(name: &str) -> bool {
+    KEYS.iter().any(|spec| {
+        spec.key
+            .split_once('.')
+            .is_some_and(|(section, _)| section == name)
+    })
+}
+
+fn read_file(path: &Path, issues: &mut Vec<ConfigIssue>) -> Table {
+    let result = std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| text.parse::<Table>().map_err(|e| e.message().to_string()));
+    result.unwrap_or_else(|reason| {
+        issues.push(ConfigIssue::File {
+            path: path.display().to_string(),
+            reason,
+        });
+        Table::new()
+    })
+}
+
+/// `{ env = "..." }`, `{ file = "..." }`, `{ command = [...] }` 형식의 비밀값 출처
+fn secret_ref_from_table(table: &Table) -> Option<SecretRef> {
+    if let Some(name) = table.get("env").and_then(Value::as_str) {
+        return Some(SecretRef::Env(name.to_string()));
+    }
+    if let Some(path) = table.get("file").and_then(Value::as_str) {
+        return Some(SecretRef::File(PathBuf::from(path)));
+    }
+    let parts: Vec<String> = match table.get("command")? {
+        Value::String(line) => line.split_whitespace().map(str::to_string).collect(),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string))
+            .collect::<Option<_>>()?,
+        _ => return None,
+    };
+    (!parts.is_empty()).then_some(SecretRef::Command(parts))
+}
+
+/// `ws.ping_interval_secs`처럼 점으로 구분된 키에 값을 넣습니다.
+fn set_path(table: &mut Table, key: &str, value: Value) {
+    match key.split_once('.') {
+        Some((section, rest)) => {
+            let entry = table
+                .entry(section)
+                .or_insert_with(|| Value::Table(Table::new()));
+            if !entry.is_table() {
+                *entry = Value::Table(Table::new());
+            }
+            if let Value::Table(inner) = entry {
+                set_path(inner, rest, value);
+            }
+        }
+        None => {
+            table.insert(key.to_string(), value);
+        }
+    }
+}
+
+fn get_path<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+    match key.split_once('.') {
+        Some((section, rest)) => get_path(table.get(section)?.as_table()?, rest),
+        None => table.get(key),
+    }
+}
+
+/// 병합된 테이블에서 값을 꺼내면서 문제를 모읍니다.
+struct Reader<'a> {
+    table: &'a Table,
+    issues: &'a mut Vec<ConfigIssue>,
+    loader: &'a ConfigLoader,
+}
+
+impl Reader<'_> {
+    fn app_config(&mut self) -> AppConfig {
+        let defaults = AppConfig::default();
+        let ws = defaults.ws;
+        let zmq = defaults.zmq;
+        let recording = defaults.recording;
+        let retry = defaults.retry;
+        let http = defaults.http;
+
+        let app_key = self.string("app_key");
+        let app_secret = self.secret("app_secret");
+        let account_no = self.account_no("account_no");
+        let environment = self.environment("", &Environment::default());
+        let token_cache_file = self
+            .string("token_cache_file")
+            .unwrap_or(defaults.token_cache_file);
+        let token_cache_key = self.secret("token_cache_key");
+        let accounts = self.accounts(&environment, &token_cache_file, &token_cache_key);
+        let default_account = self.string("default_account");
+
+        let mut config = AppConfig {
+            app_key: app_key.clone().unwrap_or_default(),
+            app_secret: app_secret.clone().unwrap_or_default(),
+            account_no,
+            environment,
+            token_cache_file,
+            token_cache_key,
+            token_issue_interval_secs: self.parse_or(
+                "token_issue_interval_secs",
+                defaults.token_issue_interval_secs,
+            ),
+            ws: WsSettings {
+                reconnect_interval_secs: self
+                    .parse_or("ws.reconnect_interval_secs", ws.reconnect_interval_secs),
+                max_reconnect_interval_secs: self.parse_or(
+                    "ws.max_reconnect_interval_secs",
+                    ws.max_reconnect_interval_secs,
+                ),
+                max_reconnect_attempts: self
+                    .parse_or("ws.max_reconnect_attempts", ws.max_reconnect_attempts),
+                max_fatal_failures: self.parse_or("ws.max_fatal_failures", ws.max_fatal_failures),
+                ping_interval_secs: self.nonzero("ws.ping_interval_secs", ws.ping_interval_secs),
+            },
+            zmq: ZmqSettings {
+                orderbook_endpoint: self
+                    .string("zmq.orderbook_endpoint")
+                    .unwrap_or(zmq.orderbook_endpoint),
+                trade_endpoint: self
+                    .string("zmq.trade_endpoint")
+                    .unwrap_or(zmq.trade_endpoint),
+            },
+            recording: RecordingSettings {
+                print_console: self.parse_or("recording.print_console", recording.print_console),
+                save_to_file: self.parse_or("recording.save_to_file", recording.save_to_file),
+                file_path: self.string("recording.file_path"),
+            },
+            retry: RetrySettings {
+                max_retries: self.parse_or("retry.max_retries", retry.max_retries),
+                initial_backoff_ms: self
+                    .parse_or("retry.initial_backoff_ms", retry.initial_backoff_ms),
+                max_backoff_ms: self.parse_or("retry.max_backoff_ms", retry.max_backoff_ms),
+                jitter: self.ratio("retry.jitter", retry.jitter),
+            },
+            http: HttpSettings {
+                connect_timeout_ms: self
+                    .parse_or("http.connect_timeout_ms", http.connect_timeout_ms),
+                timeout_ms: self.parse_or("http.timeout_ms", http.timeout_ms),
+                pool_max_idle_per_host: self
+                    .parse_or("http.pool_max_idle_per_host", http.pool_max_idle_per_host),
+                pool_idle_timeout_secs: self
+                    .parse_or("http.pool_idle_timeout_secs", http.pool_idle_timeout_secs),
+                tcp_keepalive_secs: self
+                    .parse_or("http.tcp_keepalive_secs", http.tcp_keepalive_secs),
+                user_agent: self.string("http.user_agent").unwrap_or(http.user_agent),
+                proxy: self.proxy("http.proxy"),
+            },
+            subscriptions: self.subscriptions("subscriptions"),
+            accounts: Vec::new(),
+            default_account: None,
+            rate_limits: self.rate_limits("rate_limits"),
+        };
+
+        // 최상위 인증 정보가 없으면 기본 계좌(default_account, 계좌가 하나면 그 계좌)를 씁니다.
+        let fallback = match &default_account {
+            Some(name) => {
+                let found = accounts.iter().find(|account| &account.name == name);
+                if found.is_none() {
+                    self.invalid(
+                        "default_account",
+                        name.clone(),
+                        "accounts에 없는 계좌입니다",
+                    );
+                }
+                found
+            }
+            None if accounts.len() == 1 => accounts.first(),
+            None => None,
+        };
+        let mut is_default = default_account.is_some();
+        match fallback {
+            Some(account) if app_key.is_none() && app_secret.is_none() => {
+                config = account.apply(&config);
+                is_default = true;
+            }
+            _ if app_key.is_none() && app_secret.is_none() && accounts.len() > 1 => {
+                self.missing("default_account");
+            }
+            _ => {
+                if app_key.is_none() {
+                    self.missing("app_key");
+                }
+                if app_secret.is_none() {
+                    self.missing("app_secret");
+                }
+            }
+        }
+        config.default_account = fallback
+            .filter(|_| is_default)
+            .map(|account| account.name.clone());
+        config.accounts = accounts;
+        config
+    }
+
+    /// `[accounts.<이름>]` 테이블들. 빠진 접속 환경/토큰 캐시 설정은 최상위 값을 따릅니다.
+    fn accounts(
+        &mut self,
+        environment: &Environment,
+        token_cache_file: &str,
+        token_cache_key: &Option<Secret>,
+    ) -> Vec<AccountConfig> {
+        let names: Vec<String> = match get_path(self.table, "accounts") {
+            None => return Vec::new(),
+            Some(Value::Table(table)) => table.keys().cloned().collect(),
+            Some(other) => {
+                self.invalid(
+                    "accounts",
+                    other.to_string(),
+                    "[accounts.<이름>] 테이블이어야 합니다",
+                );
+                return Vec::new();
+            }
+        };
+
+        let mut accounts = Vec::new();
+        for name in names {
+            let prefix = format!("accounts.{}.", name);
+            if !is_valid_account_name(&name) {
+                self.invalid(
+                    "accounts",
+                    name,
+                    "계좌 이름은 영문/숫자/`_`/`-`만 사용할 수 있습니다",
+                );
+                continue;
+            }
+            let key = |field: &str| format!("{}{}", prefix, field);
+            accounts.push(AccountConfig {
+                app_key: self.required(&key("app_key")),
+                app_secret: self.secret(&key("app_secret")).unwrap_or_else(|| {
+                    self.missing(&key("app_secret"));
+                    Secret::default()
+                }),
+                account_no: self.account_no(&key("account_no")),
+                environment: self.environment(&prefix, environment),
+                token_cache_file: self
+                    .string(&key("token_cache_file"))
+                    .unwrap_or_else(|| token_cache_file.to_string()),
+                token_cache_key: self
+                    .secret(&key("token_cache_key"))
+                    .or_else(|| token_cache_key.clone()),
+                name,
+            });
+        }
+        accounts
+    }
+
+    /// 계좌번호. `-`와 공백은 빼고 숫자만 남깁니다.
+    fn account_no(&mut self, key: &str) -> Option<String> {
+        let raw = self.string(key)?;
+        let digits: String = raw
+            .chars()
+            .filter(|c| *c != '-' && !c.is_whitespace())
+            .collect();
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            self.invalid(key, raw, "계좌번호는 숫자만 사용할 수 있습니다");
+            return None;
+        }
+        Some(digits)
+    }
+
+    /// 단일 값을 문자열로 꺼냅니다. (TOML 숫자/불리언도 허용, 빈 문자열은 없는 값)
+    fn string(&mut self, key: &str) -> Option<String> {
+        match get_path(self.table, key)? {
+            Value::String(s) => Some(s.clone()).filter(|s| !s.is_empty()),
+            Value::Integer(n) => Some(n.to_string()),
+            Value::Float(n) => Some(n.to_string()),
+            Value::Boolean(b) => Some(b.to_string()),
+            other => {
+                self.invalid(key, other.to_string(), "문자열/숫자/불리언이어야 합니다");
+                None
+            }
+        }
+    }
+
+    /// 비밀값. `env:`/`file:`/`cmd:` 문자열이나 `{ env = "..." }` 같은 테이블이면 그 출처에서 읽습니다.
+    ///
+    /// 읽기에 실패하면 문제를 기록하고 빈 값을 돌려줍니다. 오류 메시지에는 출처만 남기고 값은 남기지 않습니다.
+    fn secret(&mut self, key: &str) -> Option<Secret> {
+        let reference = match get_path(self.table, key)? {
+            Value::String(s) if s.is_empty() => return None,
+            Value::String(s) => match SecretRef::parse(s) {
+                Some(reference) => reference,
+                None => return Some(Secret::from(s.as_str())),
+            },
+            Value::Table(table) => match secret_ref_from_table(table) {
+                Some(reference) => reference,
+                None => {
+                    self.invalid(
+                        key,
+                        String::new(),
+                        "env/file/command 중 하나를 지정해야 합니다",
+                    );
+                    return Some(Secret::default());
+                }
+            },
+            _ => {
+                self.invalid(key, String::new(), "문자열이나 테이블이어야 합니다");
+                return Some(Secret::default());
+            }
+        };
+        // 환경변수 출처는 로더에 넘긴 환경변수에서 찾습니다.
+        let result = match &reference {
+            SecretRef::Env(name) => self
+                .loader
+                .env_var(name)
+                .map(Secret::from)
+                .ok_or_else(|| SecretError::EnvNotFound(name.clone())),
+            other => other.provider().secret(),
+        };
+        Some(result.unwrap_or_else(|e| {
+            self.invalid(key, reference.to_string(), &e.to_string());
+            Secret::default()
+        }))
+    }
+
+    /// 프록시 주소. reqwest가 받아들이지 않는 주소면 문제로 기록합니다.
+    fn proxy(&mut self, key: &str) -> Option<String> {
+        let url = self.string(key)?;
+        match reqwest::Proxy::all(&url) {
+            Ok(_) => Some(url),
+            Err(e) => {
+                self.invalid(key, url, &e.to_string());
+                None
+            }
+        }
+    }
+
+    fn required(&mut self, key: &str) -> String {
+        self.string(key).unwrap_or_else(|| {
+            self.missing(key);
+            String::new()
+        })
+    }
+
+    fn missing(&mut self, key: &str) {
+        self.issues.push(ConfigIssue::Missing {
+            key: key.to_string(),
+        });
+    }
+
+    fn parse_or<T>(&mut self, key: &str, default: T) -> T
+    where
+        T: std::str::FromStr,
+        T::Err: fmt::Display,
+    {
+        let Some(raw) = self.string(key) else {
+            return default;
+        };
+        match raw.trim().parse() {
+            Ok(value) => value,
+            Err(e) => {
+                self.invalid(key, raw, &e.to_string());
+                default
+            }
+        }
+    }
+
+    /// 0.0~1.0 사이의 비율. NaN/무한대나 범위를 벗어난 값은 문제로 기록하고 `default`를 씁니다.
+    fn ratio(&mut self, key: &str, default: f64) -> f64 {
+        let value = self.parse_or(key, default);
+        if (0.0..=1.0).contains(&value) {
+            value
+        } else {
+            self.invalid(key, value.to_string(), "0.0 이상 1.0 이하여야 합니다");
+            default
+        }
+    }
+
+    /// 0이 아닌 정수. 0은 문제로 기록하고 `default`를 씁니다. (주기로 쓰는 값)
+    fn nonzero(&mut self, key: &str, default: u64) -> u64 {
+        match self.parse_or(key, default) {
+            0 => {
+                self.invalid(key, "0".to_string(), "0보다 커야 합니다");
+                default
+            }
+            value => value,
+        }
+    }
+
+    fn invalid(&mut self, key: &str, value: String, reason: &str) {
+        self.issues.push(ConfigIssue::Invalid {
+            key: key.to_string(),
+            value,
+            reason: reason.to_string(),
+        });
+    }
+
+    /// `environment`(prod/demo/custom)로 접속 환경을 정합니다. 값이 없으면 `default`를 씁니다.
+    ///
+    /// custom이면 `rest_url`/`ws_url`을 쓰고, 빠진 쪽은 운영 주소를 씁니다.
+    /// `environment` 없이 주소만 있으면 custom으로 봅니다. (예전 `XING_TOKEN_DOMAI` 설정 호환)
+    fn environment(&mut self, prefix: &str, default: &Environment) -> Environment {
+        let key = format!("{}environment", prefix);
+        let rest_url = self.string(&format!("{}rest_url", prefix));
+        let ws_url = self.string(&format!("{}ws_url", prefix));
+        let has_url = rest_url.is_some() || ws_url.is_some();
+
+        match self.string(&key) {
+            Some(name) if name.trim().eq_ignore_ascii_case("custom") => {}
+            None if has_url => {}
+            None => return default.clone(),
+            Some(name) => {
+                return match name.parse::<Environment>() {
+                    Ok(environment) => {
+                        if has_url {
+                            self.invalid(
+                                &key,
+                                name,
+                                "rest_url/ws_url은 custom 환경에서만 사용합니다",
+                            );
+                        }
+                        environment
+                    }
+                    Err(e) => {
+                        self.invalid(&key, name, &e.to_string());
+                        default.clone()
+                    }
+                };
+            }
+        }
+        Environment::Custom {
+            rest_url: rest_url.unwrap_or_else(|| Environment::Prod.rest_base_url().to_string()),
+            ws_url: ws_url.unwrap_or_else(|| Environment::Prod.ws_url()),
+        }
+    }
+
+    fn subscriptions(&mut self, key: &str) -> Vec<SubscriptionSetting> {
+        let entries: Vec<(String, String)> = match get_path(self.table, key) {
+            None => return Vec::new(),
+            // 환경변수/CLI: "UH1:005930,US3:000660"
+            Some(Value::String(list)) => list
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| {
+                    let (tr_cd, tr_key) = item.split_once(':').unwrap_or((item, ""));
+                    (tr_cd.trim().to_string(), tr_key.trim().to_string())
+                })
+                .collect(),
+            // TOML: [[subscriptions]]
+            Some(Value::Array(items)) => items
+                .iter()
+                .map(|item| {
+                    let field = |name: &str| {
+                        item.get(name)
+                            .and_then(Value::as_str)
+                            .unwrap_or_default()
+                            .to_string()
+                    };
+                    (field("tr_cd"), field("key"))
+                })
+                .collect(),
+            Some(other) => {
+                self.invalid(
+                    key,
+                    other.to_string(),
+                    "TR:KEY 목록이나 테이블 배열이어야 합니다",
+                );
+                return Vec::new();
+            }
+        };
+
+        let mut subscriptions = Vec::new();
+        for (i, (tr_cd, tr_key)) in entries.into_iter().enumerate() {
+            let item_key = format!("{}[{}]", key, i);
+            let tr_cd = match tr_cd.parse::<TrCode>() {
+                Ok(tr_cd) => tr_cd,
+                Err(e) => {
+                    self.invalid(&item_key, tr_cd, &e.to_string());
+                    continue;
+                }
+            };
+            let setting = SubscriptionSetting { tr_cd, key: tr_key };
+            match setting.tr_key() {
+                Ok(_) => subscriptions.push(setting),
+                Err(e) => self.invalid(
+                    &item_key,
+                    format!("{}:{}", setting.tr_cd, setting.key),
+                    &e.to_string(),
+                ),
+            }
+        }
+        subscriptions
+    }
+
+    fn rate_limits(&mut self, key: &str) -> BTreeMap<String, u32> {
+        let entries: Vec<(String, String)> = match get_path(self.table, key) {
+            None => return BTreeMap::new(),
+            // 환경변수/CLI: "t1102:5,t8412:1"
+            Some(Value::String(list)) => list
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| {
+                    let (tr_cd, per_sec) = item.split_once(':').unwrap_or((item, ""));
+                    (tr_cd.trim().to_string(), per_sec.trim().to_string())
+                })
+                .collect(),
+            // TOML: [rate_limits] t1102 = 5
+            Some(Value::Table(table)) => table
+                .iter()
+                .map(|(tr_cd, per_sec)| {
+                    let per_sec = match per_sec {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (tr_cd.clone(), per_sec)
+                })
+                .collect(),
+            Some(other) => {
+                self.invalid(
+                    key,
+                    other.to_string(),
+                    "TR:초당호출수 목록이나 테이블이어야 합니다",
+                );
+                return BTreeMap::new();
+            }
+        };
+
+        let mut limits = BTreeMap::new();
+        for (tr_cd, per_sec) in entries {
+            let item_key = format!("{}.{}", key, tr_cd);
+            if tr_cd.is_empty() {
+                self.invalid(key, per_sec, "tr_cd가 비어 있습니다");
+                continue;
+            }
+            match per_sec.parse::<u32>() {
+                Ok(per_sec) => {
+                    limits.insert(tr_cd, per_sec);
+                }
+                Err(e) => self.invalid(&item_key, per_sec, &e.to_string()),
+            }
+        }
+        limits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{temp_path, write_toml};
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_file_then_env_then_cli() {
+        let path = write_toml(
+            "layers",
+            r#"
+app_key = "file-key"
+app_secret = "file-secret"
+environment = "demo"
+
+[ws]
+ping_interval_secs = 30
+max_reconnect_attempts = 5
+
+[zmq]
+orderbook_endpoint = "tcp://127.0.0.1:6000"
+
+[retry]
+max_retries = 5
+jitter = 0.5
+
+[[subscriptions]]
+tr_cd = "UH1"
+key = "005930"
+
+[[subscriptions]]
+tr_cd = "SC1"
+"#,
+        );
+        let config = ConfigLoader::new()
+            .file(&path)
+            .env_vars(vars(&[
+                ("XING_APP_SECRET", "env-secret"),
+                ("XING_WS_PING_INTERVAL_SECS", "20"),
+            ]))
+            .args(args(&[
+                "--ws.ping-interval-secs=10",
+                "--recording.save_to_file",
+                "true",
+            ]))
+            .load()
+            .unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(config.app_key, "file-key");
+        assert_eq!(config.app_secret.expose(), "env-secret");
+        assert_eq!(config.environment, Environment::Demo);
+        assert_eq!(config.ws.ping_interval_secs, 10);
+        assert_eq!(config.ws.max_reconnect_attempts, 5);
+        assert_eq!(config.ws.reconnect_interval_secs, 5);
+        assert_eq!(config.zmq.orderbook_endpoint, "tcp://127.0.0.1:6000");
+        assert_eq!(config.zmq.trade_endpoint, "tcp://0.0.0.0:5558");
+        assert!(config.recording.save_to_file);
+        assert!(config.recording.print_console);
+        assert_eq!(config.token_cache_file, DEFAULT_TOKEN_CACHE_FILE);
+        assert_eq!(
+            config.subscriptions,
+            vec![
+                SubscriptionSetting {
+                    tr_cd: TrCode::UniOrderbook,
+                    key: "005930".to_string(),
+                },
+                SubscriptionSetting {
+                    tr_cd: TrCode::OrderExecution,
+                    key: String::new(),
+                },
+            ]
+        );
+        assert_eq!(
+            config.subscriptions[0].tr_key().unwrap().as_str(),
+            "U005930   "
+        );
+
+        let client = config.client_config();
+        assert_eq!(client.url, Environment::Demo.ws_url());
+        assert_eq!(client.ping_interval, Duration::from_secs(10));
+        assert_eq!(client.max_reconnect_attempts, 5);
+
+        let retry = config.retry_policy();
+        assert_eq!(retry.max_retries, 5);
+        assert_eq!(retry.jitter, 0.5);
+        assert_eq!(
+            retry.initial_backoff,
+            RetryPolicy::default().initial_backoff
+        );
+    }
+
+    #[test]
+    fn test_reports_every_problem_at_once() {
+        let path = write_toml(
+            "unknown_keys",
+            r#"
+app_kye = "typo"
+
+[ws]
+ping_intervall_secs = 30
+
+[wss]
+ping_interval_secs = 30
+"#,
+        );
+        let err = ConfigLoader::new()
+            .file(&path)
+            .env_vars(vars(&[
+                ("XING_ENV", "staging"),
+                ("XING_WS_PING_INTERVAL_SECS", "soon"),
+                ("XING_RECORDING_SAVE_TO_FILE", "maybe"),
+                ("XING_SUBSCRIPTIONS", "UH1:005930,XXX:1,US3:12"),
+            ]))
+            .args(args(&["--unknown-flag=1"]))
+            .load()
+            .unwrap_err();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(err.missing_keys(), vec!["app_key", "app_secret"]);
+        assert_eq!(
+            err.invalid_keys(),
+            vec![
+                "environment",
+                "ws.ping_interval_secs",
+                "recording.save_to_file",
+                "subscriptions[1]",
+                "subscriptions[2]",
+            ]
+        );
+        assert!(
+            err.issues
+                .contains(&ConfigIssue::UnknownArg("--unknown-flag=1".to_string()))
+        );
+        for key in ["app_kye", "ws.ping_intervall_secs", "wss"] {
+            assert!(
+                err.issues
+                    .contains(&ConfigIssue::UnknownKey(key.to_string())),
+                "{}",
+                key
+            );
+        }
+
+        let message = err.to_string();
+        assert!(message.starts_with("설정 오류 11건"), "{}", message);
+        assert!(message.contains("XING_APP_KEY"));
+        assert!(message.contains("알 수 없는 설정 키: ws.ping_intervall_secs"));
+        assert_eq!(message.lines().count(), 12);
+    }
+
+    #[test]
+    fn test_retry_jitter_must_be_a_ratio() {
+        for jitter in ["NaN", "inf", "1.5", "-0.1"] {
+            let err = ConfigLoader::new()
+                .env_vars(vars(&[
+                    ("XING_APP_KEY", "k"),
+                    ("XING_APP_SECRET", "s"),
+                    ("XING_RETRY_JITTER", jitter),
+                ]))
+                .load()
+                .unwrap_err();
+            assert_eq!(err.invalid_keys(), vec!["retry.jitter"], "{}", jitter);
+        }
+
+        let config = ConfigLoader::new()
+            .env_vars(vars(&[
+                ("XING_APP_KEY", "k"),
+                ("XING_APP_SECRET", "s"),
+                ("XING_RETRY_JITTER", "1"),
+            ]))
+            .load()
+            .unwrap();
+        assert_eq!(config.retry.jitter, 1.0);
+    }
+
+    #[test]
+    fn test_ping_interval_must_be_positive() {
+        let err = ConfigLoader::new()
+            .env_vars(vars(&[
+                ("XING_APP_KEY", "k"),
+                ("XING_APP_SECRET", "s"),
+                ("XING_WS_PING_INTERVAL_SECS", "0"),
+            ]))
+            .load()
+            .unwrap_err();
+        assert_eq!(err.invalid_keys(), vec!["ws.ping_interval_secs"]);
+
+        let config = ConfigLoader::new()
+            .env_vars(vars(&[
+                ("XING_APP_KEY", "k"),
+                ("XING_APP_SECRET", "s"),
+                ("XING_WS_PING_INTERVAL_SECS", "1"),
+            ]))
+            .load()
+            .unwrap();
+        assert_eq!(config.ws.ping_interval_secs, 1);
+    }
+
+    #[test]
+    fn test_rate_limits() {
+        let path = write_toml(
+            "rate_limits",
+            r#"
+app_key = "k"
+app_secret = "s"
+
+[rate_limits]
+t1102 = 5
+t8412 = 0
+"#,
+        );
+        let config = ConfigLoader::new().file(&path).load().unwrap();
+        assert_eq!(
+            config.rate_limits,
+            BTreeMap::from([("t1102".to_string(), 5), ("t8412".to_string(), 0)])
+        );
+
+        // 환경변수/CLI는 목록 전체를 바꿉니다.
+        let config = ConfigLoader::new()
+            .file(&path)
+            .args(args(&["--rate-limits", "t9945:3, t1101:20"]))
+            .load()
+            .unwrap();
+        assert_eq!(
+            config.rate_limits,
+            BTreeMap::from([("t1101".to_string(), 20), ("t9945".to_string(), 3)])
+        );
+
+        let err = ConfigLoader::new()
+            .file(&path)
+            .env_vars(vars(&[("XING_RATE_LIMITS", "t1102:fast,t8412:-1")]))
+            .load()
+            .unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            err.invalid_keys(),
+            vec!["rate_limits.t1102", "rate_limits.t8412"]
+        );
+    }
+
+    #[test]
+    fn test_http_settings() {
+        let config = ConfigLoader::new()
+            .env_vars(vars(&[
+                ("XING_APP_KEY", "k"),
+                ("XING_APP_SECRET", "s"),
+                ("XING_HTTP_TIMEOUT_MS", "3000"),
+                ("XING_HTTP_PROXY", "http://proxy.local:3128"),
+            ]))
+            .args(args(&["--http.user-agent=my-bot/2.0"]))
+            .load()
+            .unwrap();
+        assert_eq!(config.http.timeout_ms, 3000);
+        assert_eq!(
+            config.http.proxy.as_deref(),
+            Some("http://proxy.local:3128")
+        );
+        assert_eq!(config.http.user_agent, "my-bot/2.0");
+        assert_eq!(
+            config.http.connect_timeout_ms,
+            HttpSettings::default().connect_timeout_ms
+        );
+        assert!(config.http_client().is_ok());
+
+        let err = ConfigLoader::new()
+            .env_vars(vars(&[
+                ("XING_APP_KEY", "k"),
+                ("XING_APP_SECRET", "s"),
+                ("XING_HTTP_PROXY", "not a url"),
+                ("XING_HTTP_POOL_MAX_IDLE_PER_HOST", "many"),
+            ]))
+            .load()
+            .unwrap_err();
+        assert_eq!(
+            err.invalid_keys(),
+            vec!["http.pool_max_idle_per_host", "http.proxy"]
+        );
+    }
+
+    #[test]
+    fn test_legacy_env_names_and_custom_environment() {
+        let config = ConfigLoader::new()
+            .env_vars(vars(&[
+                ("XING_APP_KEY", "k"),
+                ("XING_SECRET_KEY", "s"),
+                ("XING_TOKEN_DOMAI", "https://proxy.local:8080"),
+                ("KIS_TOKEN_CACHE_FILE", "legacy.json"),
+                ("XING_SUBSCRIPTIONS", "UH1:005930, US3:000660"),
+            ]))
+            .load()
+            .unwrap();
+        assert_eq!(config.app_secret.expose(), "s");
+        assert_eq!(config.token_cache_file, "legacy.json");
+        assert_eq!(config.rest_base_url(), "https://proxy.local:8080");
+        assert_eq!(config.ws_url(), Environment::Prod.ws_url());
+        assert_eq!(config.subscriptions.len(), 2);
+
+        // 현재 이름이 예전 이름보다 우선
+        let config = ConfigLoader::new()
+            .env_vars(vars(&[
+                ("XING_APP_KEY", "k"),
+                ("XING_APP_SECRET", "new"),
+                ("XING_SECRET_KEY", "old"),
+            ]))
+            .load()
+            .unwrap();
+        assert_eq!(config.app_secret.expose(), "new");
+        assert_eq!(config.environment, Environment::Prod);
+
+        // prod/demo에 주소를 함께 주면 오류
+        let err = ConfigLoader::new()
+            .env_vars(vars(&[
+                ("XING_APP_KEY", "k"),
+                ("XING_APP_SECRET", "s"),
+                ("XING_ENV", "demo"),
+                ("XING_REST_URL", "https://proxy.local"),
+            ]))
+            .load()
+            .unwrap_err();
+        assert_eq!(err.invalid_keys(), vec!["environment"]);
+    }
+
+    #[test]
+    fn test_accounts() {
+        let path = write_toml(
+            "accounts",
+            r#"
+app_key = "top-key"
+app_secret = "top-secret"
+environment = "demo"
+token_cache_file = "shared.json"
+
+[accounts.main]
+app_key = "key-main"
+app_secret = "secret-main"
+account_no = "555-01234-501"
+environment = "prod"
+token_cache_file = "main.json"
+
+[accounts.paper]
+app_key = "key-paper"
+app_secret = "secret-paper"
+"#,
+        );
+        let config = ConfigLoader::new()
+            .file(&path)
+            .args(args(&["--accounts.paper.account-no", "20012345601"]))
+            .load()
+            .unwrap();
+
+        // 최상위 인증 정보가 있으면 그대로 사용
+        assert_eq!(config.app_key, "top-key");
+        assert_eq!(config.default_account, None);
+        assert_eq!(config.accounts.len(), 2);
+
+        let main = config.account("main").unwrap();
+        assert_eq!(main.app_key, "key-main");
+        assert_eq!(main.account_no.as_deref(), Some("55501234501"));
+        assert_eq!(main.environment, Environment::Prod);
+        assert_eq!(main.token_cache_file, "main.json");
+
+        // 빠진 값은 최상위 설정을 따름
+        let paper = config.account("paper").unwrap();
+        assert_eq!(paper.account_no.as_deref(), Some("20012345601"));
+        assert_eq!(paper.environment, Environment::Demo);
+        assert_eq!(paper.token_cache_file, "shared.json");
+        assert!(config.account("other").is_none());
+
+        // 최상위 인증 정보가 없고 계좌가 여럿이면 default_account 필요
+        let err = ConfigLoader::new()
+            .file(&path)
+            .env_vars(vars(&[("XING_APP_KEY", ""), ("XING_ACCOUNT_NO", "12-ab")]))
+            .args(args(&[
+                "--app_key=",
+                "--app_secret=",
+                "--accounts.main.app_secret=",
+            ]))
+            .load()
+            .unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            err.missing_keys(),
+            vec!["accounts.main.app_secret", "default_account"]
+        );
+        assert_eq!(err.invalid_keys(), vec!["account_no"]);
+    }
+
+    #[test]
+    fn test_secret_sources() {
+        let secret_file = temp_path("config_secret");
+        std::fs::write(&secret_file, "file-secret\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&secret_file, std::fs::Permissions::from_mode(0o600)).unwrap();
+        }
+        let path = write_toml(
+            "secrets",
+            &format!(
+                r#"
+app_key = "k"
+app_secret = {{ file = "{}" }}
+token_cache_key = "env:LS_CACHE_KEY"
+
+[accounts.main]
+app_key = "key-main"
+app_secret = {{ env = "LS_MAIN_SECRET" }}
+"#,
+                secret_file
+            ),
+        );
+        let config = ConfigLoader::new()
+            .file(&path)
+            .env_vars(vars(&[
+                ("LS_CACHE_KEY", "cache-key"),
+                ("LS_MAIN_SECRET", "main-secret"),
+            ]))
+            .load()
+            .unwrap();
+        assert_eq!(config.app_secret.expose(), "file-secret");
+        assert_eq!(
+            config.token_cache_key.as_ref().map(Secret::expose),
+            Some("cache-key")
+        );
+        assert_eq!(
+            config.account("main").unwrap().app_secret.expose(),
+            "main-secret"
+        );
+
+        // Debug 출력에 비밀값이 드러나지 않음
+        let debug = format!("{:?}", config);
+        for secret in ["file-secret", "cache-key", "main-secret"] {
+            assert!(!debug.contains(secret), "{}", debug);
+        }
+
+        // 출처를 읽지 못하면 출처만 보여 주고 값이 없다고 하지는 않음
+        let err = ConfigLoader::new()
+            .file(&path)
+            .env_vars(vars(&[("XING_APP_SECRET", "env:LS_MISSING")]))
+            .load()
+            .unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&secret_file);
+        assert!(err.missing_keys().is_empty());
+        assert_eq!(
+            err.invalid_keys(),
+            vec!["app_secret", "token_cache_key", "accounts.main.app_secret"]
+        );
+        assert!(err.to_string().contains("env:LS_MISSING"));
+    }
+
+    #[test]
+    fn test_file_errors_are_reported() {
+        let missing = env::temp_dir().join("xing_config_does_not_exist.toml");
+        let err = ConfigLoader::new()
+            .file(&missing)
+            .env_vars(vars(&[("XING_APP_KEY", "k"), ("XING_APP_SECRET", "s")]))
+            .load()
+            .unwrap_err();
+        assert!(matches!(err.issues[..], [ConfigIssue::File { .. }]));
+
+        let broken = write_toml("broken", "app_key = ");
+        let err = ConfigLoader::new()
+            .args(args(&["--config", broken.to_str().unwrap()]))
+            .load()
+            .unwrap_err();
+        let _ = std::fs::remove_file(&broken);
+        assert!(matches!(err.issues[0], ConfigIssue::File { .. }));
+        assert_eq!(err.missing_keys(), vec!["app_key", "app_secret"]);
     }
 }
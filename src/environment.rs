@@ -0,0 +1,158 @@
+// 접속 환경(운영/모의투자/직접 지정)
+// REST/WebSocket 주소를 한곳에서 결정해 모의투자 키도 코드 수정 없이 사용할 수 있도록 합니다.
+
+use crate::constant::{
+    LS_REST_DOMAIN_DEMO, LS_REST_DOMAIN_PROD, LS_WS_DOMAIN_DEMO, LS_WS_DOMAIN_PROD, LS_WS_ENDPOINT,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// 접속 환경
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    /// 운영(실전투자)
+    #[default]
+    Prod,
+    /// 모의투자
+    Demo,
+    /// 주소 직접 지정 (테스트 서버, 프록시 등)
+    Custom {
+        /// REST base URL (예: `https://localhost:8080`)
+        rest_url: String,
+        /// WebSocket 전체 URL (예: `wss://localhost:9443/websocket`)
+        ws_url: String,
+    },
+}
+
+impl Environment {
+    /// REST API base URL (`/oauth2/token` 등 경로 앞부분)
+    pub fn rest_base_url(&self) -> &str {
+        match self {
+            Environment::Prod => LS_REST_DOMAIN_PROD,
+            Environment::Demo => LS_REST_DOMAIN_DEMO,
+            Environment::Custom { rest_url, .. } => rest_url.trim_end_matches('/'),
+        }
+    }
+
+    /// WebSocket 접속 URL (엔드포인트 포함)
+    pub fn ws_url(&self) -> String {
+        match self {
+            Environment::Prod => format!("{}{}", LS_WS_DOMAIN_PROD, LS_WS_ENDPOINT),
+            Environment::Demo => format!("{}{}", LS_WS_DOMAIN_DEMO, LS_WS_ENDPOINT),
+            Environment::Custom { ws_url, .. } => ws_url.clone(),
+        }
+    }
+
+    /// WebSocket 포트 (운영 9443, 모의투자 29443)
+    pub fn ws_port(&self) -> Option<u16> {
+        let url = self.ws_url();
+        let authority = url.split("://").nth(1)?.split('/').next()?;
+        match authority.rsplit_once(':') {
+            Some((_, port)) => port.parse().ok(),
+            None if url.starts_with("wss://") => Some(443),
+            None => Some(80),
+        }
+    }
+
+    pub fn is_demo(&self) -> bool {
+        matches!(self, Environment::Demo)
+    }
+
+    /// 캐시 키 등에 쓰는 짧은 이름
+    pub fn name(&self) -> &'static str {
+        match self {
+            Environment::Prod => "prod",
+            Environment::Demo => "demo",
+            Environment::Custom { .. } => "custom",
+        }
+    }
+}
+
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Environment::Custom { rest_url, .. } => write!(f, "custom({})", rest_url),
+            other => f.write_str(other.name()),
+        }
+    }
+}
+
+/// 알 수 없는 환경 이름
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnvironmentError(pub String);
+
+impl fmt::Display for ParseEnvironmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "알 수 없는 접속 환경: {} (prod/demo 중 하나, 직접 지정은 URL 설정 필요)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseEnvironmentError {}
+
+impl FromStr for Environment {
+    type Err = ParseEnvironmentError;
+
+    /// "prod"/"real"/"운영", "demo"/"mock"/"모의" (대소문자 무시)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "prod" | "production" | "real" | "운영" | "실전" => Ok(Environment::Prod),
+            "demo" | "mock" | "paper" | "모의" | "모의투자" => Ok(Environment::Demo),
+            _ => Err(ParseEnvironmentError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derived_urls() {
+        assert_eq!(
+            Environment::Prod.ws_url(),
+            "wss://openapi.ls-sec.co.kr:9443/websocket"
+        );
+        assert_eq!(
+            Environment::Demo.ws_url(),
+            "wss://openapi.ls-sec.co.kr:29443/websocket"
+        );
+        assert_eq!(Environment::Prod.ws_port(), Some(9443));
+        assert_eq!(Environment::Demo.ws_port(), Some(29443));
+        assert_eq!(
+            Environment::Demo.rest_base_url(),
+            "https://openapi.ls-sec.co.kr:8080"
+        );
+
+        let custom = Environment::Custom {
+            rest_url: "http://127.0.0.1:8080/".to_string(),
+            ws_url: "ws://127.0.0.1:9000/websocket".to_string(),
+        };
+        assert_eq!(custom.rest_base_url(), "http://127.0.0.1:8080");
+        assert_eq!(custom.ws_port(), Some(9000));
+        assert_eq!(custom.to_string(), "custom(http://127.0.0.1:8080/)");
+    }
+
+    #[test]
+    fn test_parse_and_serde() {
+        assert_eq!("DEMO".parse::<Environment>(), Ok(Environment::Demo));
+        assert_eq!("모의투자".parse::<Environment>(), Ok(Environment::Demo));
+        assert_eq!("real".parse::<Environment>(), Ok(Environment::Prod));
+        assert!("staging".parse::<Environment>().is_err());
+
+        assert_eq!(
+            serde_json::from_str::<Environment>(r#""demo""#).unwrap(),
+            Environment::Demo
+        );
+        let custom: Environment = serde_json::from_str(
+            r#"{"custom":{"rest_url":"http://a","ws_url":"ws://b/websocket"}}"#,
+        )
+        .unwrap();
+        assert_eq!(custom.rest_base_url(), "http://a");
+    }
+}
@@ -0,0 +1,531 @@
+// LS REST TR 클라이언트
+// 인증/연속조회 헤더 구성, HTTP 상태와 rsp_cd 확인, OutBlock 해석을 TR 공통으로 처리합니다.
+
+use crate::auth::error::redact;
+use crate::auth::token_manager::TokenManager;
+use crate::config::AppConfig;
+use crate::constant::LS_RSP_CD_OK;
+use crate::http::error::RestError;
+use crate::http::execute_api_call;
+use crate::http::retry::{RetryPolicy, retry};
+use crate::http::throttle::TrThrottle;
+use crate::http::tr::{TrRequest, TrResponse};
+use futures::Stream;
+use futures::stream;
+use log::debug;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
+use reqwest::{Client, Method};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// 연속 조회 최대 페이지 수 기본값
+pub const DEFAULT_MAX_PAGES: usize = 100;
+
+/// 요청마다 붙일 접근 토큰
+enum TokenSource {
+    Static(String),
+    Manager(Arc<TokenManager>),
+}
+
+/// `TrRequest`를 실행하는 REST 클라이언트
+pub struct LsRestClient {
+    client: Client,
+    base_url: String,
+    token: TokenSource,
+    throttle: Arc<TrThrottle>,
+    retry: RetryPolicy,
+}
+
+impl LsRestClient {
+    /// 요청마다 `TokenManager`에서 유효한 토큰을 받아 씁니다.
+    ///
+    /// `client`는 `AppConfig::http_client()`로 한 번 만들어 다른 구성요소와 같이 쓰세요.
+    pub fn new(client: Client, config: &AppConfig, tokens: Arc<TokenManager>) -> Self {
+        Self {
+            client,
+            base_url: config.rest_base_url().to_string(),
+            token: TokenSource::Manager(tokens),
+            throttle: Arc::new(TrThrottle::from_config(config)),
+            retry: config.retry_policy(),
+        }
+    }
+
+    /// 이미 발급받은 토큰을 그대로 씁니다.
+    pub fn with_token(client: Client, config: &AppConfig, access_token: impl Into<String>) -> Self {
+        Self {
+            client,
+            base_url: config.rest_base_url().to_string(),
+            token: TokenSource::Static(access_token.into()),
+            throttle: Arc::new(TrThrottle::from_config(config)),
+            retry: config.retry_policy(),
+        }
+    }
+
+    /// 다른 클라이언트와 호출 제한을 공유합니다. 같은 app_key로 나가는 요청은 한 `TrThrottle`을 써야 합니다.
+    pub fn with_throttle(mut self, throttle: Arc<TrThrottle>) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// 조회 TR(`TrRequest::READ_ONLY`)에 쓸 재시도 정책
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// TR별 호출 제한과 대기 통계
+    pub fn throttle(&self) -> &Arc<TrThrottle> {
+        &self.throttle
+    }
+
+    /// TR을 한 번 호출합니다. 연속 조회면 이전 응답의 `tr_cont_key`를 넘기세요.
+    ///
+    /// 보내기 전에 TR별 호출 제한에 걸리지 않을 때까지 기다립니다.
+    /// 조회 TR은 타임아웃/5xx/호출 건수 초과 시 재시도 정책에 따라 다시 보내고, 그 밖의 TR은 한 번만 보냅니다.
+    pub async fn request<T: TrRequest>(
+        &self,
+        request: &T,
+        tr_cont_key: Option<&str>,
+    ) -> Result<TrResponse<T::OutBlock>, RestError> {
+        if T::READ_ONLY {
+            retry(&self.retry, T::TR_CD, || self.send(request, tr_cont_key)).await
+        } else {
+            self.send(request, tr_cont_key).await
+        }
+    }
+
+    async fn send<T: TrRequest>(
+        &self,
+        request: &T,
+        tr_cont_key: Option<&str>,
+    ) -> Result<TrResponse<T::OutBlock>, RestError> {
+        let token = self.access_token().await?;
+        let headers = headers(T::TR_CD, &token, tr_cont_key)?;
+        let body = serde_json::json!({
+            format!("{}InBlock", T::TR_CD): request.in_block(),
+        });
+        let url = format!("{}{}", self.base_url, T::PATH);
+        debug!("{} 요청: {} {}", T::TR_CD, url, body);
+
+        self.throttle.acquire(T::TR_CD).await;
+        let resp = execute_api_call(
+            &self.client,
+            &url,
+            Method::POST,
+            Some(headers),
+            Some(body),
+            None,
+        )
+        .await?;
+        let status = resp.status();
+        let tr_cont = header_str(resp.headers(), "tr_cont") == "Y";
+        let tr_cont_key = header_str(resp.headers(), "tr_cont_key").to_string();
+        let text = resp.text().await?;
+        debug!("{} 응답 ({}): {}", T::TR_CD, status, redact(&text));
+
+        if !status.is_success() {
+            if status.as_u16() == 401
+                && let TokenSource::Manager(tokens) = &self.token
+            {
+                tokens.invalidate(&token);
+            }
+            return Err(RestError::Http {
+                status: status.as_u16(),
+                body: text,
+            });
+        }
+
+        let mut parsed: Value = serde_json::from_str(&text)
+            .map_err(|e| RestError::InvalidResponse(format!("{}: {}", T::TR_CD, e)))?;
+        let field = |parsed: &Value, name: &str| {
+            parsed
+                .get(name)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let rsp_cd = field(&parsed, "rsp_cd");
+        let rsp_msg = field(&parsed, "rsp_msg");
+        if !rsp_cd.is_empty() && rsp_cd != LS_RSP_CD_OK {
+            return Err(RestError::Api { rsp_cd, rsp_msg });
+        }
+
+        let out_key = format!("{}OutBlock", T::TR_CD);
+        let out_block = out_block(parsed.get_mut(&out_key).map(Value::take))
+            .map_err(|e| RestError::InvalidResponse(format!("{}: {}", out_key, e)))?;
+        Ok(TrResponse {
+            rsp_cd,
+            rsp_msg,
+            tr_cont,
+            tr_cont_key,
+            out_block,
+        })
+    }
+
+    /// TR을 한 번 호출하고 OutBlock만 돌려줍니다.
+    pub async fn call<T: TrRequest>(&self, request: &T) -> Result<T::OutBlock, RestError> {
+        Ok(self.request(request, None).await?.out_block)
+    }
+
+    /// 응답 헤더의 `tr_cont`/`tr_cont_key`를 따라가며 페이지별 OutBlock을 돌려주는 Stream
+    ///
+    /// 서버가 더 있다고 하는데 `max_pages`만큼 받았으면 `RestError::PageLimit`을 마지막으로 내보내고 끝냅니다.
+    /// 에러가 나면 그 에러를 내보내고 끝냅니다.
+    pub fn pages<'a, T: TrRequest>(
+        &'a self,
+        request: &'a T,
+        max_pages: usize,
+    ) -> impl Stream<Item = Result<T::OutBlock, RestError>> + 'a {
+        // 상태: (다음 요청에 넘길 tr_cont_key, 받은 페이지 수). None이면 끝
+        stream::unfold(Some((None::<String>, 0usize)), move |state| async move {
+            let (tr_cont_key, fetched) = state?;
+            if fetched >= max_pages {
+                let err = RestError::PageLimit {
+                    tr_cd: T::TR_CD,
+                    max_pages,
+                };
+                return Some((Err(err), None));
+            }
+            match self.request(request, tr_cont_key.as_deref()).await {
+                Ok(resp) => {
+                    let next = (resp.tr_cont && !resp.tr_cont_key.is_empty())
+                        .then_some((Some(resp.tr_cont_key), fetched + 1));
+                    Some((Ok(resp.out_block), next))
+                }
+                Err(e) => Some((Err(e), None)),
+            }
+        })
+    }
+
+    async fn access_token(&self) -> Result<String, RestError> {
+        match &self.token {
+            TokenSource::Static(token) => Ok(token.clone()),
+            TokenSource::Manager(tokens) => Ok(tokens.token().await?),
+        }
+    }
+}
+
+fn headers(tr_cd: &str, token: &str, tr_cont_key: Option<&str>) -> Result<HeaderMap, RestError> {
+    let value = |v: &str| {
+        HeaderValue::from_str(v)
+            .map_err(|e| RestError::InvalidRequest(format!("헤더 값 오류: {}", e)))
+    };
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static("application/json; charset=utf-8"),
+    );
+    headers.insert(AUTHORIZATION, value(&format!("Bearer {}", token))?);
+    headers.insert("tr_cd", value(tr_cd)?);
+    headers.insert(
+        "tr_cont",
+        HeaderValue::from_static(if tr_cont_key.is_some() { "Y" } else { "N" }),
+    );
+    headers.insert("tr_cont_key", value(tr_cont_key.unwrap_or_default())?);
+    Ok(headers)
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> &'a str {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+}
+
+/// OutBlock이 없거나 null이면 조회 결과가 없는 것으로 보고 빈 값(null 또는 빈 배열)으로 해석합니다.
+fn out_block<O: serde::de::DeserializeOwned>(block: Option<Value>) -> Result<O, serde_json::Error> {
+    match block {
+        Some(Value::Null) | None => serde_json::from_value(Value::Null)
+            .or_else(|_| serde_json::from_value(Value::Array(Vec::new()))),
+        Some(block) => serde_json::from_value(block),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::mock_config;
+    use futures::{StreamExt, TryStreamExt};
+    use serde::{Deserialize, Serialize};
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(Serialize)]
+    struct EchoInBlock {
+        shcode: String,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct EchoOutBlock {
+        price: i64,
+    }
+
+    struct Echo {
+        shcode: &'static str,
+    }
+
+    impl TrRequest for Echo {
+        const TR_CD: &'static str = "t0000";
+        const PATH: &'static str = "/stock/echo";
+        type InBlock = EchoInBlock;
+        type OutBlock = Vec<EchoOutBlock>;
+
+        fn in_block(&self) -> EchoInBlock {
+            EchoInBlock {
+                shcode: self.shcode.to_string(),
+            }
+        }
+    }
+
+    /// 같은 TR을 조회 TR로 표시한 것
+    struct ReadOnlyEcho;
+
+    impl TrRequest for ReadOnlyEcho {
+        const TR_CD: &'static str = "t0000";
+        const PATH: &'static str = "/stock/echo";
+        const READ_ONLY: bool = true;
+        type InBlock = EchoInBlock;
+        type OutBlock = Vec<EchoOutBlock>;
+
+        fn in_block(&self) -> EchoInBlock {
+            EchoInBlock {
+                shcode: "005930".to_string(),
+            }
+        }
+    }
+
+    fn client(server: &MockServer) -> LsRestClient {
+        let config = AppConfig {
+            // 테스트 서버는 호출 제한이 없음
+            rate_limits: [("t0000".to_string(), 0)].into(),
+            ..mock_config(server)
+        };
+        LsRestClient::with_token(config.http_client().unwrap(), &config, "tok")
+    }
+
+    #[tokio::test]
+    async fn test_request_builds_headers_and_parses_out_block() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/stock/echo"))
+            .and(header("authorization", "Bearer tok"))
+            .and(header("tr_cd", "t0000"))
+            .and(header("tr_cont", "Y"))
+            .and(header("tr_cont_key", "k1"))
+            .and(body_json(
+                serde_json::json!({"t0000InBlock": {"shcode": "005930"}}),
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("tr_cont", "Y")
+                    .insert_header("tr_cont_key", "k2")
+                    .set_body_json(serde_json::json!({
+                        "rsp_cd": "00000",
+                        "rsp_msg": "정상 처리",
+                        "t0000OutBlock": [{"price": 70000}],
+                    })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let resp = client(&server)
+            .request(&Echo { shcode: "005930" }, Some("k1"))
+            .await
+            .unwrap();
+        assert_eq!(resp.out_block, vec![EchoOutBlock { price: 70000 }]);
+        assert!(resp.tr_cont);
+        assert_eq!(resp.tr_cont_key, "k2");
+        assert_eq!(resp.rsp_msg, "정상 처리");
+    }
+
+    fn page(tr_cont: &str, key: &str, price: i64) -> ResponseTemplate {
+        ResponseTemplate::new(200)
+            .insert_header("tr_cont", tr_cont)
+            .insert_header("tr_cont_key", key)
+            .set_body_json(serde_json::json!({
+                "rsp_cd": "00000",
+                "rsp_msg": "정상 처리",
+                "t0000OutBlock": [{"price": price}],
+            }))
+    }
+
+    #[tokio::test]
+    async fn test_pages_follow_continuation_headers() {
+        let server = MockServer::start().await;
+        Mock::given(header("tr_cont", "N"))
+            .respond_with(page("Y", "k2", 1))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(header("tr_cont", "Y"))
+            .and(header("tr_cont_key", "k2"))
+            .respond_with(page("Y", "k3", 2))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(header("tr_cont", "Y"))
+            .and(header("tr_cont_key", "k3"))
+            .respond_with(page("N", "", 3))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let throttle = Arc::new(TrThrottle::new(&[("t0000".to_string(), 100)].into()));
+        let client = client(&server).with_throttle(Arc::clone(&throttle));
+        let request = Echo { shcode: "005930" };
+        let pages: Vec<_> = client
+            .pages(&request, DEFAULT_MAX_PAGES)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(
+            pages,
+            vec![
+                vec![EchoOutBlock { price: 1 }],
+                vec![EchoOutBlock { price: 2 }],
+                vec![EchoOutBlock { price: 3 }],
+            ]
+        );
+        // 페이지마다 호출 제한을 거침
+        assert_eq!(throttle.stats("t0000").unwrap().requests, 3);
+    }
+
+    #[tokio::test]
+    async fn test_pages_stop_at_max_pages() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(page("Y", "again", 1))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let client = client(&server);
+        let request = Echo { shcode: "005930" };
+        let results: Vec<_> = client.pages(&request, 2).collect().await;
+        assert_eq!(results.len(), 3);
+        assert!(results[..2].iter().all(Result::is_ok));
+        assert!(matches!(
+            results[2],
+            Err(RestError::PageLimit { max_pages: 2, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_errors() {
+        let server = MockServer::start().await;
+        Mock::given(header("tr_cont", "N"))
+            .and(body_json(
+                serde_json::json!({"t0000InBlock": {"shcode": "000000"}}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "rsp_cd": "IGW00121",
+                "rsp_msg": "조회 실패",
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(body_json(
+            serde_json::json!({"t0000InBlock": {"shcode": "999999"}}),
+        ))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&server)
+        .await;
+        Mock::given(body_json(
+            serde_json::json!({"t0000InBlock": {"shcode": "111111"}}),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "rsp_cd": "00000",
+            "rsp_msg": "조회 내역 없음",
+        })))
+        .mount(&server)
+        .await;
+
+        let client = client(&server);
+        let err = client.call(&Echo { shcode: "000000" }).await.unwrap_err();
+        assert!(matches!(&err, RestError::Api { rsp_cd, .. } if rsp_cd == "IGW00121"));
+
+        let err = client.call(&Echo { shcode: "999999" }).await.unwrap_err();
+        assert_eq!(err.status(), Some(401));
+        assert!(err.to_string().contains("UNAUTHORIZED"));
+
+        // OutBlock이 없으면 빈 결과
+        let out = client.call(&Echo { shcode: "111111" }).await.unwrap();
+        assert!(out.is_empty());
+    }
+
+    fn fast_retry() -> RetryPolicy {
+        RetryPolicy {
+            initial_backoff: std::time::Duration::from_millis(1),
+            max_backoff: std::time::Duration::from_millis(1),
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_only_tr_is_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "rsp_cd": "IGW00201",
+                "rsp_msg": "초당 전송 건수 초과",
+            })))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(page("N", "", 1))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let out = client(&server)
+            .with_retry_policy(fast_retry())
+            .call(&ReadOnlyEcho)
+            .await
+            .unwrap();
+        assert_eq!(out, vec![EchoOutBlock { price: 1 }]);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(4)
+            .mount(&server)
+            .await;
+        let err = client(&server)
+            .with_retry_policy(fast_retry())
+            .call(&ReadOnlyEcho)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(500));
+    }
+
+    #[tokio::test]
+    async fn test_non_read_only_tr_is_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let err = client(&server)
+            .with_retry_policy(fast_retry())
+            .call(&Echo { shcode: "005930" })
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(503));
+    }
+}
@@ -0,0 +1,91 @@
+// REST TR 호출 에러 타입
+
+use crate::auth::error::AuthError;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum RestError {
+    /// 연결 실패, 타임아웃 등 전송 계층 에러
+    Network(reqwest::Error),
+    /// 2xx가 아닌 HTTP 응답
+    Http { status: u16, body: String },
+    /// rsp_cd가 정상(00000)이 아닌 응답
+    Api { rsp_cd: String, rsp_msg: String },
+    /// 요청을 만들 수 없음 (헤더에 쓸 수 없는 값 등)
+    InvalidRequest(String),
+    /// 성공 응답이지만 본문을 해석할 수 없음
+    InvalidResponse(String),
+    /// 연속 조회가 최대 페이지 수를 넘음
+    PageLimit {
+        tr_cd: &'static str,
+        max_pages: usize,
+    },
+    /// 접근 토큰을 얻지 못함
+    Auth(AuthError),
+}
+
+impl RestError {
+    /// HTTP 상태코드 (HTTP 에러일 때만)
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            RestError::Http { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+}
+
+fn status_message(status: u16) -> &'static str {
+    match status {
+        400 => "잘못된 요청입니다 (BAD_REQUEST)",
+        401 => "인증이 필요하거나 토큰이 잘못되었습니다 (UNAUTHORIZED)",
+        404 => "API 엔드포인트를 찾을 수 없습니다 (NOT_FOUND)",
+        405 => "허용되지 않은 메서드입니다 (METHOD_NOT_ALLOWED)",
+        500 => "서버 내부 오류입니다 (INTERNAL_SERVER_ERROR)",
+        503 => "서비스를 사용할 수 없습니다 (SERVICE_UNAVAILABLE)",
+        _ => "알 수 없는 HTTP 에러입니다",
+    }
+}
+
+impl fmt::Display for RestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestError::Network(e) => write!(f, "네트워크 오류: {}", e),
+            RestError::Http { status, .. } => {
+                write!(f, "HTTP {}: {}", status, status_message(*status))
+            }
+            RestError::Api { rsp_cd, rsp_msg } => {
+                write!(f, "API Error rsp_cd: {}, rsp_msg: {}", rsp_cd, rsp_msg)
+            }
+            RestError::InvalidRequest(msg) => write!(f, "요청 생성 실패: {}", msg),
+            RestError::InvalidResponse(msg) => write!(f, "응답 해석 실패: {}", msg),
+            RestError::PageLimit { tr_cd, max_pages } => write!(
+                f,
+                "{} 연속 조회가 최대 {}페이지를 넘었습니다",
+                tr_cd, max_pages
+            ),
+            RestError::Auth(e) => write!(f, "접근 토큰 오류: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for RestError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RestError::Network(e) => Some(e),
+            RestError::Auth(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for RestError {
+    fn from(e: reqwest::Error) -> Self {
+        RestError::Network(e)
+    }
+}
+
+impl From<AuthError> for RestError {
+    fn from(e: AuthError) -> Self {
+        RestError::Auth(e)
+    }
+}
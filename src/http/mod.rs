@@ -0,0 +1,100 @@
+pub mod client;
+pub mod error;
+pub mod retry;
+pub mod throttle;
+pub mod tr;
+
+use crate::config::HttpSettings;
+use reqwest::{Client, Method, Response};
+use serde_json::Value;
+use std::time::Duration;
+
+/// `[http]` 설정으로 HTTP 클라이언트를 만듭니다. (타임아웃, 연결 풀, keep-alive, User-Agent, 프록시)
+pub fn build_client(settings: &HttpSettings) -> Result<Client, reqwest::Error> {
+    let mut builder = Client::builder()
+        .connect_timeout(Duration::from_millis(settings.connect_timeout_ms))
+        .pool_max_idle_per_host(settings.pool_max_idle_per_host)
+        .pool_idle_timeout(Duration::from_secs(settings.pool_idle_timeout_secs))
+        .user_agent(settings.user_agent.as_str());
+    if settings.timeout_ms > 0 {
+        builder = builder.timeout(Duration::from_millis(settings.timeout_ms));
+    }
+    if settings.tcp_keepalive_secs > 0 {
+        builder = builder.tcp_keepalive(Duration::from_secs(settings.tcp_keepalive_secs));
+    }
+    if let Some(proxy) = &settings.proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    builder.build()
+}
+
+/// 공통 HTTP API 호출 함수 (POST/GET, 헤더/바디/쿼리 지원)
+pub async fn execute_api_call(
+    client: &Client,
+    url: &str,
+    method: Method,
+    headers: Option<reqwest::header::HeaderMap>,
+    body: Option<Value>,
+    query: Option<&[(&str, &str)]>,
+) -> Result<Response, reqwest::Error> {
+    let mut req = client.request(method, url);
+    if let Some(h) = headers {
+        req = req.headers(h);
+    }
+    if let Some(q) = query {
+        req = req.query(q);
+    }
+    if let Some(b) = body {
+        req = req.json(&b);
+    }
+    let resp = req.send().await?;
+    Ok(resp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_build_client_applies_settings() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("user-agent", "xing-test/1.0"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_millis(500)))
+            .mount(&server)
+            .await;
+
+        let client = build_client(&HttpSettings {
+            timeout_ms: 100,
+            user_agent: "xing-test/1.0".to_string(),
+            ..HttpSettings::default()
+        })
+        .unwrap();
+        let resp = execute_api_call(&client, &server.uri(), Method::GET, None, None, None)
+            .await
+            .unwrap();
+        assert!(resp.status().is_success());
+
+        // 제한 시간을 넘기면 타임아웃
+        let err = execute_api_call(&client, &server.uri(), Method::POST, None, None, None)
+            .await
+            .unwrap_err();
+        assert!(err.is_timeout());
+    }
+
+    #[test]
+    fn test_build_client_rejects_bad_proxy() {
+        let settings = HttpSettings {
+            proxy: Some("not a url".to_string()),
+            ..HttpSettings::default()
+        };
+        assert!(build_client(&settings).is_err());
+    }
+}
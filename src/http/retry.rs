@@ -0,0 +1,194 @@
+// REST 재시도 정책
+// 타임아웃/연결 실패, 5xx, LS 호출 건수 초과 응답은 백오프 후 다시 보냅니다. 조회 TR(`TrRequest::READ_ONLY`)에만 적용합니다.
+
+use crate::constant::LS_RSP_CD_RATE_LIMITED;
+use crate::http::error::RestError;
+use crate::websocket::backoff::Backoff;
+use log::{info, warn};
+use std::future::Future;
+use std::time::Duration;
+
+/// 재시도 횟수와 대기시간
+///
+/// n번째 재시도 전 대기시간은 `min(max_backoff, initial_backoff * multiplier^n)`에서 `jitter` 비율만큼 무작위로 줄인 값입니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// 첫 시도 뒤 추가로 보내는 최대 횟수. 0이면 재시도하지 않음
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub multiplier: f64,
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 재시도하지 않는 정책
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    fn backoff(&self) -> Backoff {
+        Backoff::new(
+            self.initial_backoff,
+            self.max_backoff,
+            self.multiplier,
+            self.jitter,
+        )
+    }
+}
+
+/// 다시 보내면 성공할 수 있는 에러인지 여부
+///
+/// 인증 실패, 4xx, 잘못된 요청/응답, 업무 에러(rsp_cd)는 다시 보내도 같으므로 재시도하지 않습니다.
+pub fn is_retryable(err: &RestError) -> bool {
+    match err {
+        RestError::Network(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+        RestError::Http { status, .. } => *status == 429 || *status >= 500,
+        RestError::Api { rsp_cd, .. } => LS_RSP_CD_RATE_LIMITED.contains(&rsp_cd.as_str()),
+        _ => false,
+    }
+}
+
+/// `attempt`를 정책에 따라 재시도합니다. 실패한 시도마다 로그를 남깁니다.
+///
+/// 재시도해도 되는(멱등인) 요청에만 쓰세요. 주문처럼 두 번 처리되면 안 되는 요청에는 쓰지 않습니다.
+pub async fn retry<T, F, Fut>(
+    policy: &RetryPolicy,
+    label: &str,
+    mut attempt: F,
+) -> Result<T, RestError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, RestError>>,
+{
+    let attempts = policy.max_retries.saturating_add(1);
+    let mut backoff = policy.backoff();
+    loop {
+        let tried = backoff.attempt() + 1;
+        match attempt().await {
+            Ok(value) => {
+                if tried > 1 {
+                    info!("{} {}번째 시도에서 성공", label, tried);
+                }
+                return Ok(value);
+            }
+            Err(e) if tried < attempts && is_retryable(&e) => {
+                let delay = backoff.next_delay();
+                warn!(
+                    "{} 실패 (시도 {}/{}), {:?} 후 재시도: {}",
+                    label, tried, attempts, delay, e
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                if tried > 1 {
+                    warn!("{} {}번 시도 후 실패: {}", label, tried, e);
+                }
+                return Err(e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn policy(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            multiplier: 2.0,
+            jitter: 0.0,
+        }
+    }
+
+    fn http(status: u16) -> RestError {
+        RestError::Http {
+            status,
+            body: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(is_retryable(&http(500)));
+        assert!(is_retryable(&http(503)));
+        assert!(is_retryable(&http(429)));
+        assert!(!is_retryable(&http(400)));
+        assert!(!is_retryable(&http(401)));
+        assert!(is_retryable(&RestError::Api {
+            rsp_cd: "IGW00201".to_string(),
+            rsp_msg: String::new(),
+        }));
+        assert!(!is_retryable(&RestError::Api {
+            rsp_cd: "IGW00121".to_string(),
+            rsp_msg: String::new(),
+        }));
+        assert!(!is_retryable(&RestError::InvalidResponse(String::new())));
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success() {
+        let calls = AtomicU32::new(0);
+        let result = retry(&policy(3), "t9945", || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(http(503)),
+                _ => Ok("ok"),
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), "ok");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up() {
+        // 재시도 횟수를 다 쓰면 마지막 에러
+        let calls = AtomicU32::new(0);
+        let err = retry(&policy(2), "t9945", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(http(500))
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err.status(), Some(500));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // 재시도할 수 없는 에러는 바로 돌려줌
+        let calls = AtomicU32::new(0);
+        let err = retry(&policy(2), "t9945", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(http(400))
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err.status(), Some(400));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let calls = AtomicU32::new(0);
+        let _ = retry(&RetryPolicy::none(), "t9945", || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(http(503))
+        })
+        .await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
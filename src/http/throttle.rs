@@ -0,0 +1,287 @@
+// TR별 호출 속도 제한
+// LS는 TR 코드마다 초당 호출 수를 제한하고 넘으면 에러로 응답하므로, 요청 전에 tr_cd별 토큰 버킷에서 차례를 기다립니다.
+
+use crate::config::AppConfig;
+use log::debug;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// 표에 없는 TR의 초당 호출 수
+pub const DEFAULT_RATE_LIMIT: u32 = 1;
+
+/// LS 안내 기준 TR별 초당 호출 수 기본값. 설정의 `[rate_limits]`로 덮어쓸 수 있습니다.
+pub const LS_RATE_LIMITS: &[(&str, u32)] = &[
+    // 현재가/호가
+    ("t1101", 10),
+    ("t1102", 10),
+    ("t8407", 2),
+    // 차트
+    ("t8410", 1),
+    ("t8411", 1),
+    ("t8412", 1),
+    // 종목 마스터
+    ("t8430", 2),
+    ("t9945", 2),
+    // 계좌 조회
+    ("t0424", 2),
+    ("CSPAQ12200", 1),
+    ("CSPAQ12300", 1),
+    // 주문
+    ("CSPAT00601", 10),
+    ("CSPAT00701", 3),
+    ("CSPAT00801", 3),
+];
+
+/// TR 하나의 대기 통계
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ThrottleStats {
+    /// 허용된 요청 수
+    pub requests: u64,
+    /// 1ms 이상 기다린 요청 수
+    pub throttled: u64,
+    /// 대기 시간 합계 (앞선 요청 뒤에서 줄 선 시간 포함)
+    pub total_wait: Duration,
+    /// 가장 오래 기다린 시간
+    pub max_wait: Duration,
+}
+
+impl ThrottleStats {
+    /// 요청당 평균 대기 시간
+    pub fn average_wait(&self) -> Duration {
+        if self.requests == 0 {
+            Duration::ZERO
+        } else {
+            self.total_wait / self.requests as u32
+        }
+    }
+
+    fn record(&mut self, waited: Duration) {
+        self.requests += 1;
+        if waited >= Duration::from_millis(1) {
+            self.throttled += 1;
+        }
+        self.total_wait += waited;
+        self.max_wait = self.max_wait.max(waited);
+    }
+}
+
+struct BucketState {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// tr_cd 하나의 토큰 버킷. 초당 `per_sec`개씩 채워지고 최대 `per_sec`개까지 쌓입니다.
+struct Bucket {
+    per_sec: u32,
+    // tokio Mutex는 잠금을 요청한 순서대로 넘겨주므로, 여러 태스크가 기다려도 먼저 온 요청이 먼저 나갑니다.
+    state: tokio::sync::Mutex<BucketState>,
+    stats: Mutex<ThrottleStats>,
+}
+
+impl Bucket {
+    fn new(per_sec: u32) -> Self {
+        Self {
+            per_sec,
+            state: tokio::sync::Mutex::new(BucketState {
+                tokens: f64::from(per_sec),
+                updated: Instant::now(),
+            }),
+            stats: Mutex::new(ThrottleStats::default()),
+        }
+    }
+
+    fn refill(&self, state: &mut BucketState) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.updated).as_secs_f64();
+        state.tokens =
+            (state.tokens + elapsed * f64::from(self.per_sec)).min(f64::from(self.per_sec));
+        state.updated = now;
+    }
+}
+
+/// tr_cd별 토큰 버킷 모음
+///
+/// 같은 app_key로 나가는 요청은 한 인스턴스를 공유해야 제한이 맞습니다. (`Session`이 계좌마다 하나씩 가집니다)
+pub struct TrThrottle {
+    limits: HashMap<String, u32>,
+    buckets: Mutex<HashMap<String, Arc<Bucket>>>,
+}
+
+impl TrThrottle {
+    /// LS 기본값에 `overrides`를 덮어쓴 제한. 0은 제한 없음입니다.
+    pub fn new(overrides: &BTreeMap<String, u32>) -> Self {
+        let mut limits: HashMap<String, u32> = LS_RATE_LIMITS
+            .iter()
+            .map(|(tr_cd, per_sec)| (tr_cd.to_string(), *per_sec))
+            .collect();
+        limits.extend(overrides.iter().map(|(k, v)| (k.clone(), *v)));
+        Self {
+            limits,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 설정의 `rate_limits`를 적용한 제한
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self::new(&config.rate_limits)
+    }
+
+    /// tr_cd의 초당 호출 수 (0이면 제한 없음)
+    pub fn limit(&self, tr_cd: &str) -> u32 {
+        self.limits
+            .get(tr_cd)
+            .copied()
+            .unwrap_or(DEFAULT_RATE_LIMIT)
+    }
+
+    /// tr_cd로 요청을 보내도 될 때까지 기다리고, 기다린 시간을 돌려줍니다.
+    pub async fn acquire(&self, tr_cd: &str) -> Duration {
+        let Some(bucket) = self.bucket(tr_cd) else {
+            return Duration::ZERO;
+        };
+        let started = Instant::now();
+        let mut state = bucket.state.lock().await;
+        bucket.refill(&mut state);
+        if state.tokens < 1.0 {
+            let wait = (1.0 - state.tokens) / f64::from(bucket.per_sec);
+            tokio::time::sleep(Duration::from_secs_f64(wait)).await;
+            bucket.refill(&mut state);
+        }
+        state.tokens = (state.tokens - 1.0).max(0.0);
+        drop(state);
+
+        let waited = started.elapsed();
+        if waited >= Duration::from_millis(1) {
+            debug!("{} 호출 제한으로 {:?} 대기", tr_cd, waited);
+        }
+        bucket.stats.lock().unwrap().record(waited);
+        waited
+    }
+
+    /// tr_cd의 대기 통계 (아직 호출이 없으면 `None`)
+    pub fn stats(&self, tr_cd: &str) -> Option<ThrottleStats> {
+        let bucket = self.buckets.lock().unwrap().get(tr_cd).cloned()?;
+        let stats = *bucket.stats.lock().unwrap();
+        Some(stats)
+    }
+
+    /// 호출한 적 있는 모든 TR의 대기 통계 (tr_cd순)
+    pub fn snapshot(&self) -> BTreeMap<String, ThrottleStats> {
+        let buckets = self.buckets.lock().unwrap();
+        buckets
+            .iter()
+            .map(|(tr_cd, bucket)| (tr_cd.clone(), *bucket.stats.lock().unwrap()))
+            .collect()
+    }
+
+    fn bucket(&self, tr_cd: &str) -> Option<Arc<Bucket>> {
+        let per_sec = self.limit(tr_cd);
+        if per_sec == 0 {
+            return None;
+        }
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets
+            .entry(tr_cd.to_string())
+            .or_insert_with(|| Arc::new(Bucket::new(per_sec)));
+        Some(Arc::clone(bucket))
+    }
+}
+
+impl Default for TrThrottle {
+    fn default() -> Self {
+        Self::new(&BTreeMap::new())
+    }
+}
+
+impl std::fmt::Debug for TrThrottle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrThrottle")
+            .field("limits", &self.limits.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throttle(limits: &[(&str, u32)]) -> Arc<TrThrottle> {
+        let overrides = limits
+            .iter()
+            .map(|(tr_cd, per_sec)| (tr_cd.to_string(), *per_sec))
+            .collect();
+        Arc::new(TrThrottle::new(&overrides))
+    }
+
+    #[test]
+    fn test_limits() {
+        let throttle = throttle(&[("t1102", 3), ("t9999", 0)]);
+        assert_eq!(throttle.limit("t1102"), 3);
+        assert_eq!(throttle.limit("t8412"), 1);
+        assert_eq!(throttle.limit("t0000"), DEFAULT_RATE_LIMIT);
+        assert_eq!(throttle.limit("t9999"), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_burst_then_refill() {
+        let throttle = throttle(&[("t1102", 2)]);
+        assert_eq!(throttle.acquire("t1102").await, Duration::ZERO);
+        assert_eq!(throttle.acquire("t1102").await, Duration::ZERO);
+        // 버킷이 비었으므로 토큰 하나가 채워질 때까지(0.5초) 대기
+        assert_eq!(throttle.acquire("t1102").await, Duration::from_millis(500));
+
+        // 다른 TR은 따로 셈
+        assert_eq!(throttle.acquire("t8412").await, Duration::ZERO);
+        // 제한 없음
+        let unlimited = TrThrottle::new(&BTreeMap::from([("t0000".to_string(), 0)]));
+        for _ in 0..10 {
+            assert_eq!(unlimited.acquire("t0000").await, Duration::ZERO);
+        }
+        assert_eq!(unlimited.stats("t0000"), None);
+
+        let stats = throttle.stats("t1102").unwrap();
+        assert_eq!(stats.requests, 3);
+        assert_eq!(stats.throttled, 1);
+        assert_eq!(stats.total_wait, Duration::from_millis(500));
+        assert_eq!(stats.max_wait, Duration::from_millis(500));
+        assert_eq!(
+            throttle.snapshot().keys().collect::<Vec<_>>(),
+            vec!["t1102", "t8412"]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_waiters_are_served_in_order() {
+        let throttle = throttle(&[("t8412", 1)]);
+        throttle.acquire("t8412").await;
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let mut handles = Vec::new();
+        for i in 0..4 {
+            let throttle = Arc::clone(&throttle);
+            let order = Arc::clone(&order);
+            handles.push(tokio::spawn(async move {
+                let waited = throttle.acquire("t8412").await;
+                order.lock().unwrap().push(i);
+                waited
+            }));
+            // 태스크가 차례대로 줄을 서도록 한 번씩 양보
+            tokio::task::yield_now().await;
+        }
+        let mut waits = Vec::new();
+        for handle in handles {
+            waits.push(handle.await.unwrap());
+        }
+
+        assert_eq!(*order.lock().unwrap(), vec![0, 1, 2, 3]);
+        assert_eq!(waits, (1..=4).map(Duration::from_secs).collect::<Vec<_>>());
+        let stats = throttle.stats("t8412").unwrap();
+        assert_eq!(stats.requests, 5);
+        assert_eq!(stats.throttled, 4);
+        assert_eq!(stats.max_wait, Duration::from_secs(4));
+        assert_eq!(stats.total_wait, Duration::from_secs(10));
+        assert_eq!(stats.average_wait(), Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,49 @@
+// REST TR 정의
+// TR 하나는 `TrRequest` 구현 하나로 표현합니다. 헤더/본문 구성과 응답 해석은 `LsRestClient`가 맡습니다.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// REST TR 요청
+///
+/// 요청 본문은 `{ "<TR_CD>InBlock": in_block() }`, 응답에서는 `"<TR_CD>OutBlock"`을 꺼내 `OutBlock`으로 해석합니다.
+///
+/// ```ignore
+/// pub struct T9945 { pub gubun: String }
+///
+/// impl TrRequest for T9945 {
+///     const TR_CD: &'static str = "t9945";
+///     const PATH: &'static str = "/stock/market-data";
+///     const READ_ONLY: bool = true;
+///     type InBlock = T9945InBlock;
+///     type OutBlock = Vec<StockItem>;
+///
+///     fn in_block(&self) -> T9945InBlock { ... }
+/// }
+/// ```
+pub trait TrRequest {
+    /// LS tr_cd (예: "t9945")
+    const TR_CD: &'static str;
+    /// REST 경로 (예: "/stock/market-data")
+    const PATH: &'static str;
+    /// 조회 TR이면 `true`. `true`인 TR만 실패 시 재시도합니다.
+    /// 주문처럼 두 번 처리되면 안 되는 TR은 기본값(`false`)으로 두세요.
+    const READ_ONLY: bool = false;
+
+    type InBlock: Serialize;
+    type OutBlock: DeserializeOwned;
+
+    fn in_block(&self) -> Self::InBlock;
+}
+
+/// TR 응답
+#[derive(Debug, Clone)]
+pub struct TrResponse<O> {
+    pub rsp_cd: String,
+    pub rsp_msg: String,
+    /// 연속 조회할 데이터가 더 있는지 (응답 헤더 `tr_cont` == "Y")
+    pub tr_cont: bool,
+    /// 다음 연속 조회에 넘길 키 (응답 헤더 `tr_cont_key`)
+    pub tr_cont_key: String,
+    pub out_block: O,
+}
@@ -1,8 +1,13 @@
 pub mod auth;
 pub mod config;
 pub mod constant;
+pub mod environment;
 pub mod http;
 pub mod quotation;
+pub mod secret;
+pub mod session;
+#[cfg(test)]
+mod test_support;
 pub mod types;
 pub mod websocket;
 pub mod zmq;
@@ -1,29 +1,36 @@
-mod auth;
-mod config;
-mod constant;
-mod http;
-mod quotation;
-mod types;
-mod websocket;
-mod zmq;
-
-use crate::auth::oauth::get_access_token;
-use crate::auth::ws_auth::get_ws_approval_key;
-use crate::config::AppConfig;
 use log::{error, info};
+use xing_trading_rust::auth::error::mask;
+use xing_trading_rust::auth::oauth::get_access_token;
+use xing_trading_rust::auth::ws_auth::get_ws_approval_key;
+use xing_trading_rust::config::AppConfig;
 
 #[tokio::main]
 async fn main() {
-    // env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
-    let config = AppConfig::from_env();
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let config = match AppConfig::load() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+
+    // 토큰 발급, TR 호출 등 모든 REST 요청이 이 클라이언트(연결 풀)를 같이 씁니다.
+    let client = match config.http_client() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("HTTP 클라이언트 생성 실패: {}", e);
+            std::process::exit(2);
+        }
+    };
 
-    match get_access_token(&config).await {
-        Ok(token) => info!("Access Token: {}", token),
+    match get_access_token(&client, &config).await {
+        Ok(token) => info!("Access Token: {}", mask(&token)),
         Err(e) => error!("토큰 발급 실패: {:?}", e),
     }
 
-    match get_ws_approval_key(&config).await {
-        Ok(key) => info!("WebSocket Approval Key: {}", key),
+    match get_ws_approval_key(&client, &config).await {
+        Ok(key) => info!("WebSocket Approval Key: {}", mask(&key)),
         Err(e) => error!("WS Approval Key 발급 실패: {:?}", e),
     }
 }
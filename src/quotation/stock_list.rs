@@ -1,5 +1,7 @@
-use crate::config::AppConfig;
-use reqwest::Client;
+use crate::http::client::{DEFAULT_MAX_PAGES, LsRestClient};
+use crate::http::error::RestError;
+use crate::http::tr::TrRequest;
+use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -13,117 +15,56 @@ pub struct StockItem {
     pub filler: String,  // filler
 }
 
-#[derive(Debug, Deserialize)]
-struct StockListResponse {
-    t9945OutBlock: Vec<StockItem>,
+/// 주식마스터조회 (t9945)
+#[derive(Debug, Clone)]
+pub struct T9945 {
+    /// "1": KSP, "2": KSD
+    pub gubun: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct T9945InBlock {
+    pub gubun: String,
+}
+
+impl TrRequest for T9945 {
+    const TR_CD: &'static str = "t9945";
+    const PATH: &'static str = "/stock/market-data";
+    const READ_ONLY: bool = true;
+    type InBlock = T9945InBlock;
+    type OutBlock = Vec<StockItem>;
+
+    fn in_block(&self) -> T9945InBlock {
+        T9945InBlock {
+            gubun: self.gubun.clone(),
+        }
+    }
 }
 
 /// HFT 환경을 고려하여, etfchk 필터링은 iterator로 처리하고,
 /// 필요시 HashMap<String, StockItem> 등으로 변환해 빠른 조회가 가능하도록 설계할 것.
+///
+/// `client`(보통 `Session::rest_client()`)의 토큰과 호출 제한을 그대로 쓰므로,
+/// 여러 번 호출해도 같은 app_key의 t9945 제한을 함께 지킵니다.
 pub async fn fetch_stock_list(
-    config: &AppConfig,
-    access_token: &str,
-    tr_cd: &str,
+    client: &LsRestClient,
     gubun: &str,              // "1" (KSP) or "2" (KSD)
     etfchk: Option<&str>,     // "0" or "1" or None(전체)
     etn_filter: Option<bool>, // Some(true): ETN만, Some(false): ETN 제외, None: 전체
     debug_print: bool,        // true면 상세 로그 출력
-) -> Result<HashMap<String, StockItem>, Box<dyn std::error::Error>> {
-    let client = Client::new();
-    let url = format!("{}/stock/market-data", config.token_url);
-
-    let mut headers = reqwest::header::HeaderMap::new();
-    headers.insert("content-type", "application/json; charset=utf-8".parse()?);
-    let bearer = format!("Bearer {}", access_token);
-    headers.insert("authorization", bearer.parse()?);
-    headers.insert("tr_cd", tr_cd.parse()?);
-    headers.insert("tr_cont", "Y".parse()?);
-    headers.insert("tr_cont_key", "".parse()?);
-
-    let body = serde_json::json!({
-        "t9945InBlock": {
-            "gubun": gubun
-        }
-    });
-
-    let resp = client
-        .post(&url)
-        .headers(headers)
-        .json(&body)
-        .send()
-        .await?;
-    // HTTP 상태코드별 에러 핸들링 추가
-    let status = resp.status();
-    if !status.is_success() {
-        let err_msg = match status.as_u16() {
-            400 => "잘못된 요청입니다 (BAD_REQUEST)",
-            401 => "인증이 필요하거나 토큰이 잘못되었습니다 (UNAUTHORIZED)",
-            404 => "API 엔드포인트를 찾을 수 없습니다 (NOT_FOUND)",
-            405 => "허용되지 않은 메서드입니다 (METHOD_NOT_ALLOWED)",
-            500 => "서버 내부 오류입니다 (INTERNAL_SERVER_ERROR)",
-            503 => "서비스를 사용할 수 없습니다 (SERVICE_UNAVAILABLE)",
-            _ => "알 수 없는 HTTP 에러입니다",
-        };
-        return Err(format!("HTTP {}: {}", status.as_u16(), err_msg).into());
-    }
-    let text = resp.text().await?;
-    if debug_print {
-        println!("[DEBUG] API raw response: {}", text);
-    }
-
-    let parsed: serde_json::Value = match serde_json::from_str(&text) {
-        Ok(val) => val,
-        Err(e) => {
-            if debug_print {
-                println!("[DEBUG] JSON 파싱 실패: {}", e);
-            }
-            return Err(Box::new(e));
-        }
+) -> Result<HashMap<String, StockItem>, RestError> {
+    let request = T9945 {
+        gubun: gubun.to_string(),
     };
-    if debug_print {
-        println!("[DEBUG] 파싱된 JSON: {:?}", parsed);
-    }
-
-    // API 에러 코드/메시지 핸들링
-    if let Some(rsp_cd) = parsed.get("rsp_cd") {
-        if rsp_cd != "00000" {
-            let msg = parsed
-                .get("rsp_msg")
-                .and_then(|v| v.as_str())
-                .unwrap_or("Unknown error");
-            if debug_print {
-                println!("[DEBUG] API 에러 rsp_cd: {}, rsp_msg: {}", rsp_cd, msg);
-            }
-            return Err(format!("API Error rsp_cd: {}, rsp_msg: {}", rsp_cd, msg).into());
-        }
-    }
-
-    let stock_list: Vec<StockItem> = match &parsed["t9945OutBlock"] {
-        serde_json::Value::Array(_) => {
-            match serde_json::from_value(parsed["t9945OutBlock"].clone()) {
-                Ok(list) => list,
-                Err(e) => {
-                    if debug_print {
-                        println!("[DEBUG] t9945OutBlock 파싱 실패: {}", e);
-                    }
-                    return Err(Box::new(e));
-                }
-            }
-        }
-        serde_json::Value::Null => {
-            if debug_print {
-                println!("[DEBUG] t9945OutBlock이 null임");
-            }
-            Vec::new()
-        }
-        _ => {
+    // 연속 조회 페이지를 모두 이어 붙임
+    let pages = client.pages(&request, DEFAULT_MAX_PAGES);
+    let stock_list: Vec<StockItem> = match pages.try_concat().await {
+        Ok(list) => list,
+        Err(e) => {
             if debug_print {
-                println!(
-                    "[DEBUG] t9945OutBlock이 배열/Null이 아님: {:?}",
-                    parsed["t9945OutBlock"]
-                );
+                println!("[DEBUG] t9945 조회 실패: {}", e);
             }
-            Vec::new()
+            return Err(e);
         }
     };
     if debug_print {
@@ -140,7 +81,7 @@ pub async fn fetch_stock_list(
         .into_iter()
         .filter(|item| {
             // etfchk 필터
-            let etfchk_pass = etfchk.map_or(true, |val| item.etfchk == val);
+            let etfchk_pass = etfchk.is_none_or(|val| item.etfchk == val);
             // etn 필터 (대소문자 무시)
             let etn_pass = match etn_filter {
                 Some(true) => item.hname.to_uppercase().contains("ETN"),
@@ -177,21 +118,96 @@ mod tests {
     use super::*;
     use crate::auth::oauth::get_access_token;
     use crate::config::AppConfig;
-    use std::collections::HashMap;
+    use crate::test_support::mock_config;
     use tokio;
+    use wiremock::matchers::header;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn item(shcode: &str, hname: &str) -> serde_json::Value {
+        serde_json::json!({
+            "hname": hname, "shcode": shcode, "expcode": "", "etfchk": "0", "nxt_chk": "1", "filler": ""
+        })
+    }
+
+    #[tokio::test]
+    async fn test_fetch_stock_list_follows_continuation() {
+        let server = MockServer::start().await;
+        Mock::given(header("tr_cont", "N"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("tr_cont", "Y")
+                    .insert_header("tr_cont_key", "next")
+                    .set_body_json(serde_json::json!({
+                        "rsp_cd": "00000",
+                        "t9945OutBlock": [item("005930", "삼성전자")],
+                    })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(header("tr_cont_key", "next"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "rsp_cd": "00000",
+                "t9945OutBlock": [item("000660", "SK하이닉스"), item("580001", "신한 ETN")],
+            })))
+            .mount(&server)
+            .await;
+        let config = mock_config(&server);
+
+        let client = LsRestClient::with_token(config.http_client().unwrap(), &config, "tok");
+        let all = fetch_stock_list(&client, "1", None, None, false)
+            .await
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        let no_etn = fetch_stock_list(&client, "1", Some("0"), Some(false), false)
+            .await
+            .unwrap();
+        assert!(no_etn.contains_key("005930") && no_etn.contains_key("000660"));
+        assert_eq!(no_etn.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_calls_share_throttle() {
+        let server = MockServer::start().await;
+        Mock::given(header("tr_cd", "t9945"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "rsp_cd": "00000",
+                "t9945OutBlock": [item("005930", "삼성전자")],
+            })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let config = AppConfig {
+            rate_limits: [("t9945".to_string(), 1)].into(),
+            ..mock_config(&server)
+        };
+
+        let client = LsRestClient::with_token(config.http_client().unwrap(), &config, "tok");
+        fetch_stock_list(&client, "1", None, None, false)
+            .await
+            .unwrap();
+        fetch_stock_list(&client, "2", None, None, false)
+            .await
+            .unwrap();
+
+        // 초당 1건이므로 두 번째 호출은 같은 throttle에서 대기한 것으로 기록됨
+        // (대기시간은 실제 시계에 따라 달라지므로 횟수만 확인)
+        let stats = client.throttle().stats("t9945").unwrap();
+        assert_eq!(stats.requests, 2);
+        assert_eq!(stats.throttled, 1);
+    }
 
     #[tokio::test]
     async fn test_fetch_stock_list_debug() {
         let config = AppConfig::from_env();
-        let token = match get_access_token(&config).await {
+        let http = config.http_client().unwrap();
+        let token = match get_access_token(&http, &config).await {
             Ok(token) => token,
             Err(e) => {
                 println!("AccessToken 발급 실패: {}", e);
                 return;
             }
         };
-        let tr_cd = "t9945";
-
+        let client = LsRestClient::with_token(http, &config, token);
         // 다양한 조합을 반복적으로 실행하여, 결과가 달라지는 경우를 모두 로그로 남김
         for gubun in &["1", "2"] {
             for etfchk in &[None, Some("0"), Some("1"), Some("999")] {
@@ -200,9 +216,7 @@ mod tests {
                         "\n[TEST] gubun: {:?}, etfchk: {:?}, etn_filter: {:?}",
                         gubun, etfchk, etn_filter
                     );
-                    let result =
-                        fetch_stock_list(&config, &token, tr_cd, gubun, *etfchk, *etn_filter, true)
-                            .await;
+                    let result = fetch_stock_list(&client, gubun, *etfchk, *etn_filter, true).await;
                     match &result {
                         Ok(map) => {
                             println!("[RESULT] 종목 수: {}", map.len());
@@ -226,8 +240,9 @@ mod tests {
     async fn test_fetch_stock_list_http_error() {
         let config = AppConfig::from_env();
         let invalid_token = "invalid_token";
-        let tr_cd = "t9945";
-        let result = fetch_stock_list(&config, invalid_token, tr_cd, "1", None, None, false).await;
+        let client =
+            LsRestClient::with_token(config.http_client().unwrap(), &config, invalid_token);
+        let result = fetch_stock_list(&client, "1", None, None, false).await;
         match result {
             Ok(_) => panic!("에러가 발생해야 합니다."),
             Err(e) => {
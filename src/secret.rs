@@ -0,0 +1,377 @@
+// 비밀값(app_secret, 토큰 캐시 암호화 키) 읽기
+// 설정에는 값 대신 출처(환경변수/파일/외부 명령)를 적을 수 있고, 읽은 값은 Debug 출력에 드러나지 않습니다.
+
+use serde::Deserialize;
+use std::fmt;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Debug 출력에서 가려지는 문자열
+///
+/// 값이 필요한 곳에서만 `expose()`로 꺼내 쓰세요.
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(****)")
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+/// 비밀값 읽기 실패
+#[derive(Debug)]
+pub enum SecretError {
+    /// 환경변수 없음
+    EnvNotFound(String),
+    /// 파일을 읽지 못함
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// 소유자 외에 읽기/쓰기 권한이 있는 파일
+    InsecurePermissions { path: PathBuf, mode: u32 },
+    /// 외부 명령 실행 실패 또는 0이 아닌 종료 코드
+    Command { command: String, reason: String },
+    /// 읽은 값이 비어 있음
+    Empty(String),
+}
+
+impl fmt::Display for SecretError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecretError::EnvNotFound(name) => write!(f, "환경변수 {} 없음", name),
+            SecretError::Io { path, source } => {
+                write!(f, "{} 읽기 실패: {}", path.display(), source)
+            }
+            SecretError::InsecurePermissions { path, mode } => write!(
+                f,
+                "{}의 권한({:o})이 너무 넓습니다. chmod 600 {} 후 다시 시도하세요",
+                path.display(),
+                mode & 0o777,
+                path.display()
+            ),
+            SecretError::Command { command, reason } => {
+                write!(f, "명령 `{}` 실패: {}", command, reason)
+            }
+            SecretError::Empty(source) => write!(f, "{}에서 읽은 값이 비어 있음", source),
+        }
+    }
+}
+
+impl std::error::Error for SecretError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SecretError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// 비밀값을 읽어 오는 출처
+pub trait SecretProvider: Send + Sync {
+    fn secret(&self) -> Result<Secret, SecretError>;
+}
+
+impl SecretProvider for Secret {
+    fn secret(&self) -> Result<Secret, SecretError> {
+        Ok(self.clone())
+    }
+}
+
+/// 환경변수에서 읽습니다.
+#[derive(Debug, Clone)]
+pub struct EnvSecret {
+    pub name: String,
+}
+
+impl EnvSecret {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl SecretProvider for EnvSecret {
+    fn secret(&self) -> Result<Secret, SecretError> {
+        std::env::var(&self.name)
+            .ok()
+            .filter(|v| !v.is_empty())
+            .map(Secret::from)
+            .ok_or_else(|| SecretError::EnvNotFound(self.name.clone()))
+    }
+}
+
+/// 파일 내용을 읽습니다. (끝의 줄바꿈 제외)
+///
+/// unix에서는 소유자 외 권한이 있는 파일(예: 0644)은 거부합니다.
+#[derive(Debug, Clone)]
+pub struct FileSecret {
+    pub path: PathBuf,
+}
+
+impl FileSecret {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl SecretProvider for FileSecret {
+    fn secret(&self) -> Result<Secret, SecretError> {
+        let io_error = |source| SecretError::Io {
+            path: self.path.clone(),
+            source,
+        };
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&self.path)
+                .map_err(io_error)?
+                .permissions()
+                .mode();
+            if mode & 0o077 != 0 {
+                return Err(SecretError::InsecurePermissions {
+                    path: self.path.clone(),
+                    mode,
+                });
+            }
+        }
+        let text = std::fs::read_to_string(&self.path).map_err(io_error)?;
+        let value = text.trim_end_matches(['\r', '\n']);
+        if value.is_empty() {
+            return Err(SecretError::Empty(self.path.display().to_string()));
+        }
+        Ok(Secret::from(value))
+    }
+}
+
+/// 외부 명령(`pass show ...` 등)의 표준출력 첫 줄을 읽습니다.
+#[derive(Debug, Clone)]
+pub struct CommandSecret {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl CommandSecret {
+    pub fn new(program: impl Into<String>, args: Vec<String>) -> Self {
+        Self {
+            program: program.into(),
+            args,
+        }
+    }
+
+    /// 공백으로 나눈 명령줄 (`"pass show ls/app_secret"`)
+    pub fn parse(command_line: &str) -> Option<Self> {
+        let mut parts = command_line.split_whitespace().map(str::to_string);
+        let program = parts.next()?;
+        Some(Self::new(program, parts.collect()))
+    }
+
+    fn command_line(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl SecretProvider for CommandSecret {
+    fn secret(&self) -> Result<Secret, SecretError> {
+        let command_error = |reason: String| SecretError::Command {
+            command: self.command_line(),
+            reason,
+        };
+        let output = Command::new(&self.program)
+            .args(&self.args)
+            .output()
+            .map_err(|e| command_error(e.to_string()))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(command_error(format!(
+                "{} ({})",
+                output.status,
+                stderr.trim()
+            )));
+        }
+        let stdout =
+            String::from_utf8(output.stdout).map_err(|_| command_error("UTF-8 아님".into()))?;
+        let value = stdout.lines().next().unwrap_or_default();
+        if value.is_empty() {
+            return Err(SecretError::Empty(self.command_line()));
+        }
+        Ok(Secret::from(value))
+    }
+}
+
+/// 설정에 적는 비밀값 출처
+///
+/// - 문자열: `env:NAME`, `file:/path/to/secret`, `cmd:pass show ls/app_secret`
+/// - TOML 테이블: `{ env = "NAME" }`, `{ file = "..." }`, `{ command = ["pass", "show", "ls/app_secret"] }`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecretRef {
+    Env(String),
+    File(PathBuf),
+    Command(Vec<String>),
+}
+
+impl SecretRef {
+    /// 접두어가 붙은 문자열이면 출처로, 아니면 `None`(값 그대로 사용)
+    pub fn parse(value: &str) -> Option<Self> {
+        if let Some(name) = value.strip_prefix("env:") {
+            Some(SecretRef::Env(name.trim().to_string()))
+        } else if let Some(path) = value.strip_prefix("file:") {
+            Some(SecretRef::File(PathBuf::from(path.trim())))
+        } else {
+            value.strip_prefix("cmd:").map(|command| {
+                SecretRef::Command(command.split_whitespace().map(str::to_string).collect())
+            })
+        }
+    }
+
+    pub fn provider(&self) -> Box<dyn SecretProvider> {
+        match self {
+            SecretRef::Env(name) => Box::new(EnvSecret::new(name.clone())),
+            SecretRef::File(path) => Box::new(FileSecret::new(path.clone())),
+            SecretRef::Command(parts) => Box::new(CommandSecret::new(
+                parts.first().cloned().unwrap_or_default(),
+                parts.iter().skip(1).cloned().collect(),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SecretRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecretRef::Env(name) => write!(f, "env:{}", name),
+            SecretRef::File(path) => write!(f, "file:{}", path.display()),
+            SecretRef::Command(parts) => write!(f, "cmd:{}", parts.join(" ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_path;
+
+    fn temp_file(name: &str, body: &str, mode: u32) -> PathBuf {
+        let path = PathBuf::from(temp_path(&format!("secret_{}", name)));
+        std::fs::write(&path, body).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let secret = Secret::from("hunter2");
+        assert_eq!(format!("{:?}", secret), "Secret(****)");
+        assert_eq!(format!("{:?}", Some(secret.clone())), "Some(Secret(****))");
+        assert_eq!(secret.expose(), "hunter2");
+    }
+
+    #[test]
+    fn test_env_secret() {
+        assert!(!EnvSecret::new("PATH").secret().unwrap().is_empty());
+        assert!(matches!(
+            EnvSecret::new("XING_TEST_SECRET_NOT_SET").secret(),
+            Err(SecretError::EnvNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_file_secret() {
+        let path = temp_file("ok", "file-secret\n", 0o600);
+        assert_eq!(
+            FileSecret::new(&path).secret().unwrap().expose(),
+            "file-secret"
+        );
+        let _ = std::fs::remove_file(&path);
+
+        let missing = FileSecret::new(std::env::temp_dir().join("xing_secret_missing"));
+        assert!(matches!(missing.secret(), Err(SecretError::Io { .. })));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_file_secret_rejects_wide_permissions() {
+        let path = temp_file("wide", "file-secret\n", 0o644);
+        let err = FileSecret::new(&path).secret().unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(err, SecretError::InsecurePermissions { .. }));
+        assert!(err.to_string().contains("644"));
+        assert!(!err.to_string().contains("file-secret"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_secret() {
+        let secret = CommandSecret::parse("printf cmd-secret\\nsecond-line")
+            .unwrap()
+            .secret()
+            .unwrap();
+        assert_eq!(secret.expose(), "cmd-secret");
+
+        let err = CommandSecret::parse("false").unwrap().secret().unwrap_err();
+        assert!(matches!(err, SecretError::Command { .. }));
+        let err = CommandSecret::parse("xing-no-such-command")
+            .unwrap()
+            .secret()
+            .unwrap_err();
+        assert!(matches!(err, SecretError::Command { .. }));
+    }
+
+    #[test]
+    fn test_parse_ref() {
+        assert_eq!(
+            SecretRef::parse("env:LS_SECRET"),
+            Some(SecretRef::Env("LS_SECRET".to_string()))
+        );
+        assert_eq!(
+            SecretRef::parse("file:/run/secrets/ls"),
+            Some(SecretRef::File(PathBuf::from("/run/secrets/ls")))
+        );
+        assert_eq!(
+            SecretRef::parse("cmd:pass show ls/app_secret"),
+            Some(SecretRef::Command(vec![
+                "pass".to_string(),
+                "show".to_string(),
+                "ls/app_secret".to_string()
+            ]))
+        );
+        assert_eq!(SecretRef::parse("plain-secret"), None);
+        assert_eq!(
+            SecretRef::parse("cmd:pass show x").unwrap().to_string(),
+            "cmd:pass show x"
+        );
+    }
+}
@@ -0,0 +1,359 @@
+// 계좌별 세션
+// 설정의 계좌마다 토큰 관리자와 설정을 묶어 두고, 이름으로 꺼내 REST/주문/실시간 구성요소를 만듭니다.
+
+use crate::auth::error::AuthError;
+use crate::auth::token_manager::TokenManager;
+use crate::config::AppConfig;
+use crate::http::client::LsRestClient;
+use crate::http::throttle::TrThrottle;
+use crate::websocket::client::{ClientConfig, WebSocketClient};
+use crate::websocket::handler::MessageHandler;
+use reqwest::Client;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+/// `[accounts]`가 없을 때 최상위 설정으로 만드는 세션 이름
+pub const DEFAULT_SESSION: &str = "default";
+
+/// 계좌 하나의 설정과 토큰 관리자, TR 호출 제한
+pub struct Session {
+    name: String,
+    config: AppConfig,
+    client: Client,
+    tokens: Arc<TokenManager>,
+    throttle: Arc<TrThrottle>,
+}
+
+impl Session {
+    /// 토큰 발급과 TR 호출은 `client`로 보냅니다.
+    pub fn new(client: Client, name: impl Into<String>, config: AppConfig) -> Self {
+        let tokens = TokenManager::new(client.clone(), config.clone());
+        let throttle = Arc::new(TrThrottle::from_config(&config));
+        Self {
+            name: name.into(),
+            config,
+            client,
+            tokens,
+            throttle,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// 이 계좌의 인증 정보/접속 환경이 적용된 설정
+    pub fn config(&self) -> &AppConfig {
+        &self.config
+    }
+
+    /// 계좌번호 (주문/잔고 TR에 사용)
+    pub fn account_no(&self) -> Option<&str> {
+        self.config.account_no.as_deref()
+    }
+
+    /// 이 세션이 쓰는 HTTP 클라이언트
+    pub fn http_client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn token_manager(&self) -> &Arc<TokenManager> {
+        &self.tokens
+    }
+
+    /// 이 계좌의 유효한 접근 토큰
+    pub async fn access_token(&self) -> Result<String, AuthError> {
+        self.tokens.token().await
+    }
+
+    /// 이 계좌의 TR 호출 제한과 대기 통계
+    pub fn throttle(&self) -> &Arc<TrThrottle> {
+        &self.throttle
+    }
+
+    /// 이 계좌의 토큰으로 TR을 호출하는 REST 클라이언트. 같은 세션의 클라이언트끼리 호출 제한을 공유합니다.
+    pub fn rest_client(&self) -> LsRestClient {
+        LsRestClient::new(self.client.clone(), &self.config, Arc::clone(&self.tokens))
+            .with_throttle(Arc::clone(&self.throttle))
+    }
+
+    /// 이 계좌의 접속 환경을 쓰는 WebSocket 클라이언트 설정
+    pub fn client_config(&self) -> ClientConfig {
+        self.config.client_config()
+    }
+
+    /// 이 계좌의 WebSocket 클라이언트. 토큰이 갱신되면 이후 요청에 새 토큰을 사용합니다.
+    pub fn websocket_client<H: MessageHandler + 'static>(&self, handler: H) -> WebSocketClient<H> {
+        WebSocketClient::new(self.client_config(), handler)
+            .with_token_updates(self.tokens.subscribe())
+    }
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Session")
+            .field("name", &self.name)
+            .field("account_no", &self.config.account_no)
+            .field("environment", &self.config.environment)
+            .finish()
+    }
+}
+
+/// 등록되지 않은 계좌 이름
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownAccount {
+    pub name: String,
+    pub available: Vec<String>,
+}
+
+impl fmt::Display for UnknownAccount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "알 수 없는 계좌: {} (사용 가능: {})",
+            self.name,
+            self.available.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownAccount {}
+
+/// 이름으로 계좌별 세션을 찾는 레지스트리
+///
+/// - `[accounts.<이름>]`마다 세션을 하나씩 만들고, 모든 세션이 `client` 하나(연결 풀)를 같이 씁니다.
+/// - 최상위 인증 정보(app_key/app_secret)가 계좌에서 온 값이 아니면 `"default"` 세션이 추가됩니다.
+/// - 기본 세션은 `default_account`(계좌가 하나면 그 계좌)이고, 없으면 `"default"` 세션입니다.
+#[derive(Debug)]
+pub struct SessionRegistry {
+    sessions: BTreeMap<String, Arc<Session>>,
+    default: String,
+}
+
+impl SessionRegistry {
+    pub fn from_config(client: &Client, config: &AppConfig) -> Self {
+        let mut sessions = BTreeMap::new();
+        for account in &config.accounts {
+            if let Some(account_config) = config.account(&account.name) {
+                sessions.insert(
+                    account.name.clone(),
+                    Arc::new(Session::new(
+                        client.clone(),
+                        account.name.clone(),
+                        account_config,
+                    )),
+                );
+            }
+        }
+        // 최상위 인증 정보가 없으면 로더가 기본 계좌의 값을 채워 두므로 그때는 세션을 따로 만들지 않습니다.
+        let from_account = config
+            .default_account
+            .as_ref()
+            .and_then(|name| config.accounts.iter().find(|a| &a.name == name))
+            .is_some_and(|account| account.app_key == config.app_key);
+        if !from_account {
+            sessions.insert(
+                DEFAULT_SESSION.to_string(),
+                Arc::new(Session::new(
+                    client.clone(),
+                    DEFAULT_SESSION,
+                    config.clone(),
+                )),
+            );
+        }
+        let default = match &config.default_account {
+            Some(name) if sessions.contains_key(name) => name.clone(),
+            _ => DEFAULT_SESSION.to_string(),
+        };
+        Self { sessions, default }
+    }
+
+    /// 이름으로 세션을 찾습니다.
+    pub fn get(&self, name: &str) -> Result<Arc<Session>, UnknownAccount> {
+        self.sessions
+            .get(name)
+            .cloned()
+            .ok_or_else(|| UnknownAccount {
+                name: name.to_string(),
+                available: self.names().map(str::to_string).collect(),
+            })
+    }
+
+    /// 기본 세션
+    pub fn default_session(&self) -> Arc<Session> {
+        Arc::clone(&self.sessions[&self.default])
+    }
+
+    /// 세션 이름 (이름순)
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.sessions.keys().map(String::as_str)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<Session>> {
+        self.sessions.values()
+    }
+
+    /// 모든 세션의 토큰 백그라운드 갱신을 시작합니다.
+    pub fn spawn_refresh_tasks(&self) -> Vec<JoinHandle<()>> {
+        self.sessions
+            .values()
+            .map(|session| session.tokens.spawn_refresh_task())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigLoader;
+    use crate::environment::Environment;
+    use crate::test_support::{temp_path, write_toml};
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn load(name: &str, body: &str) -> AppConfig {
+        let path = write_toml(&format!("session_{}", name), body);
+        let config = ConfigLoader::new().file(&path).load().unwrap();
+        let _ = std::fs::remove_file(path);
+        config
+    }
+
+    #[test]
+    fn test_sessions_per_account() {
+        let config = load(
+            "accounts",
+            r#"
+default_account = "main"
+
+[accounts.main]
+app_key = "key-main"
+app_secret = "secret-main"
+account_no = "555-01234-501"
+
+[accounts.paper]
+app_key = "key-paper"
+app_secret = "secret-paper"
+account_no = "20012345601"
+environment = "demo"
+"#,
+        );
+        assert_eq!(config.app_key, "key-main");
+
+        let registry = SessionRegistry::from_config(&Client::new(), &config);
+        assert_eq!(registry.names().collect::<Vec<_>>(), vec!["main", "paper"]);
+        assert_eq!(registry.default_session().name(), "main");
+
+        let main = registry.get("main").unwrap();
+        assert_eq!(main.account_no(), Some("55501234501"));
+        assert_eq!(main.config().environment, Environment::Prod);
+
+        let paper = registry.get("paper").unwrap();
+        assert_eq!(paper.config().app_key, "key-paper");
+        assert_eq!(paper.account_no(), Some("20012345601"));
+        assert_eq!(paper.client_config().url, Environment::Demo.ws_url());
+
+        let err = registry.get("other").unwrap_err();
+        assert_eq!(err.available, vec!["main", "paper"]);
+        assert!(err.to_string().contains("other"));
+    }
+
+    #[test]
+    fn test_default_session_without_accounts() {
+        let config = ConfigLoader::new()
+            .env_vars(vec![
+                ("XING_APP_KEY".to_string(), "k".to_string()),
+                ("XING_APP_SECRET".to_string(), "s".to_string()),
+            ])
+            .load()
+            .unwrap();
+        let registry = SessionRegistry::from_config(&Client::new(), &config);
+        assert_eq!(registry.names().collect::<Vec<_>>(), vec![DEFAULT_SESSION]);
+        assert_eq!(registry.default_session().config().app_key, "k");
+    }
+
+    #[test]
+    fn test_top_level_credentials_with_default_account() {
+        let config = load(
+            "top_level",
+            r#"
+app_key = "key-top"
+app_secret = "secret-top"
+default_account = "main"
+
+[accounts.main]
+app_key = "key-main"
+app_secret = "secret-main"
+
+[accounts.paper]
+app_key = "key-paper"
+app_secret = "secret-paper"
+"#,
+        );
+        let registry = SessionRegistry::from_config(&Client::new(), &config);
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            vec![DEFAULT_SESSION, "main", "paper"]
+        );
+        assert_eq!(registry.default_session().name(), "main");
+        assert_eq!(
+            registry.get(DEFAULT_SESSION).unwrap().config().app_key,
+            "key-top"
+        );
+        assert_eq!(registry.get("main").unwrap().config().app_key, "key-main");
+    }
+
+    #[tokio::test]
+    async fn test_tokens_are_issued_per_account() {
+        let server = MockServer::start().await;
+        for account in ["a", "b"] {
+            Mock::given(method("POST"))
+                .and(path("/oauth2/token"))
+                .and(body_string_contains(format!("appkey=key-{}", account)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "access_token": format!("tok-{}", account),
+                    "expires_in": 86400,
+                    "scope": "oob",
+                    "token_type": "Bearer",
+                })))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let cache_file = temp_path("session_tokens.json");
+        let config = load(
+            "tokens",
+            &format!(
+                r#"
+default_account = "a"
+environment = "custom"
+rest_url = "{}"
+token_cache_file = "{}"
+token_issue_interval_secs = 0
+
+[accounts.a]
+app_key = "key-a"
+app_secret = "secret-a"
+
+[accounts.b]
+app_key = "key-b"
+app_secret = "secret-b"
+"#,
+                server.uri(),
+                cache_file
+            ),
+        );
+
+        let registry = SessionRegistry::from_config(&Client::new(), &config);
+        assert_eq!(registry.default_session().name(), "a");
+        let a = registry.get("a").unwrap();
+        let b = registry.get("b").unwrap();
+        assert_eq!(a.access_token().await.unwrap(), "tok-a");
+        assert_eq!(b.access_token().await.unwrap(), "tok-b");
+        // 두 번째 호출은 각자 보관 중인 토큰 사용
+        assert_eq!(a.access_token().await.unwrap(), "tok-a");
+        let _ = std::fs::remove_file(&cache_file);
+    }
+}
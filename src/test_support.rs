@@ -0,0 +1,37 @@
+// 테스트 공용 도우미
+// 임시 파일 경로, 임시 설정 파일, mock 서버를 바라보는 설정을 한 곳에서 만듭니다.
+
+use crate::config::AppConfig;
+use crate::environment::Environment;
+use std::path::PathBuf;
+use wiremock::MockServer;
+
+/// 프로세스마다 겹치지 않는 임시 파일 경로 (`<임시 디렉터리>/xing_<name>_<pid>`)
+///
+/// 이전 실행에서 남은 파일은 지워 둡니다. 테스트끼리 병렬로 돌기 때문에 `name`은 테스트마다 달라야 합니다.
+pub fn temp_path(name: &str) -> String {
+    let path = std::env::temp_dir()
+        .join(format!("xing_{}_{}", name, std::process::id()))
+        .to_string_lossy()
+        .into_owned();
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// `body`를 담은 임시 TOML 설정 파일
+pub fn write_toml(name: &str, body: &str) -> PathBuf {
+    let path = PathBuf::from(temp_path(&format!("{}.toml", name)));
+    std::fs::write(&path, body).unwrap();
+    path
+}
+
+/// REST 요청을 `server`로 보내는 기본 설정
+pub fn mock_config(server: &MockServer) -> AppConfig {
+    AppConfig {
+        environment: Environment::Custom {
+            rest_url: server.uri(),
+            ws_url: String::new(),
+        },
+        ..AppConfig::default()
+    }
+}
@@ -0,0 +1,325 @@
+// OrderbookMessage(평면 80여개 필드)를 호가 단계 배열로 다루기 위한 구조화 타입과 호가 분석 함수
+
+use crate::types::orderbook::OrderbookMessage;
+use serde::{Deserialize, Serialize};
+
+/// 호가 단계 수
+pub const BOOK_DEPTH: usize = 10;
+
+/// 잔량 집계 시장 구분
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Venue {
+    /// KRX 잔량
+    Krx,
+    /// NXT 잔량
+    Nxt,
+    /// 통합(KRX + NXT) 잔량
+    Unified,
+}
+
+/// 매도/매수 구분
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// 매도 호가
+    Ask,
+    /// 매수 호가
+    Bid,
+}
+
+/// 시장별 잔량
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VenueQty {
+    pub krx: i64,
+    pub nxt: i64,
+    pub unt: i64,
+}
+
+impl VenueQty {
+    pub fn get(&self, venue: Venue) -> i64 {
+        match venue {
+            Venue::Krx => self.krx,
+            Venue::Nxt => self.nxt,
+            Venue::Unified => self.unt,
+        }
+    }
+}
+
+/// 호가 한 단계 (가격 + 시장별 잔량)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Level {
+    pub price: f64,
+    pub qty: VenueQty,
+}
+
+impl Level {
+    fn new(price: f64, krx: i64, nxt: i64, unt: i64) -> Self {
+        Self {
+            price,
+            qty: VenueQty { krx, nxt, unt },
+        }
+    }
+
+    /// 가격이 0이면 비어있는 호가로 간주합니다.
+    pub fn is_empty(&self) -> bool {
+        self.price <= 0.0
+    }
+}
+
+/// 10단계 호가창
+///
+/// `asks[0]`/`bids[0]`이 최우선 호가입니다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderBook {
+    pub shcode: String,
+    pub hotime: String,
+    pub asks: [Level; BOOK_DEPTH],
+    pub bids: [Level; BOOK_DEPTH],
+    pub total_ask: VenueQty,
+    pub total_bid: VenueQty,
+}
+
+impl From<&OrderbookMessage> for OrderBook {
+    fn from(m: &OrderbookMessage) -> Self {
+        let asks = [
+            Level::new(
+                m.offerho1,
+                m.krx_offerrem1,
+                m.nxt_offerrem1,
+                m.unt_offerrem1,
+            ),
+            Level::new(
+                m.offerho2,
+                m.krx_offerrem2,
+                m.nxt_offerrem2,
+                m.unt_offerrem2,
+            ),
+            Level::new(
+                m.offerho3,
+                m.krx_offerrem3,
+                m.nxt_offerrem3,
+                m.unt_offerrem3,
+            ),
+            Level::new(
+                m.offerho4,
+                m.krx_offerrem4,
+                m.nxt_offerrem4,
+                m.unt_offerrem4,
+            ),
+            Level::new(
+                m.offerho5,
+                m.krx_offerrem5,
+                m.nxt_offerrem5,
+                m.unt_offerrem5,
+            ),
+            Level::new(
+                m.offerho6,
+                m.krx_offerrem6,
+                m.nxt_offerrem6,
+                m.unt_offerrem6,
+            ),
+            Level::new(
+                m.offerho7,
+                m.krx_offerrem7,
+                m.nxt_offerrem7,
+                m.unt_offerrem7,
+            ),
+            Level::new(
+                m.offerho8,
+                m.krx_offerrem8,
+                m.nxt_offerrem8,
+                m.unt_offerrem8,
+            ),
+            Level::new(
+                m.offerho9,
+                m.krx_offerrem9,
+                m.nxt_offerrem9,
+                m.unt_offerrem9,
+            ),
+            Level::new(
+                m.offerho10,
+                m.krx_offerrem10,
+                m.nxt_offerrem10,
+                m.unt_offerrem10,
+            ),
+        ];
+        let bids = [
+            Level::new(m.bidho1, m.krx_bidrem1, m.nxt_bidrem1, m.unt_bidrem1),
+            Level::new(m.bidho2, m.krx_bidrem2, m.nxt_bidrem2, m.unt_bidrem2),
+            Level::new(m.bidho3, m.krx_bidrem3, m.nxt_bidrem3, m.unt_bidrem3),
+            Level::new(m.bidho4, m.krx_bidrem4, m.nxt_bidrem4, m.unt_bidrem4),
+            Level::new(m.bidho5, m.krx_bidrem5, m.nxt_bidrem5, m.unt_bidrem5),
+            Level::new(m.bidho6, m.krx_bidrem6, m.nxt_bidrem6, m.unt_bidrem6),
+            Level::new(m.bidho7, m.krx_bidrem7, m.nxt_bidrem7, m.unt_bidrem7),
+            Level::new(m.bidho8, m.krx_bidrem8, m.nxt_bidrem8, m.unt_bidrem8),
+            Level::new(m.bidho9, m.krx_bidrem9, m.nxt_bidrem9, m.unt_bidrem9),
+            Level::new(m.bidho10, m.krx_bidrem10, m.nxt_bidrem10, m.unt_bidrem10),
+        ];
+        Self {
+            shcode: m.shcode.clone(),
+            hotime: m.hotime.clone(),
+            asks,
+            bids,
+            total_ask: VenueQty {
+                krx: m.krx_totofferrem,
+                nxt: m.nxt_totofferrem,
+                unt: m.unt_totofferrem,
+            },
+            total_bid: VenueQty {
+                krx: m.krx_totbidrem,
+                nxt: m.nxt_totbidrem,
+                unt: m.unt_totbidrem,
+            },
+        }
+    }
+}
+
+impl From<OrderbookMessage> for OrderBook {
+    fn from(m: OrderbookMessage) -> Self {
+        Self::from(&m)
+    }
+}
+
+impl OrderBook {
+    pub fn levels(&self, side: Side) -> &[Level; BOOK_DEPTH] {
+        match side {
+            Side::Ask => &self.asks,
+            Side::Bid => &self.bids,
+        }
+    }
+
+    /// 최우선 매도호가 (비어있으면 None)
+    pub fn best_ask(&self) -> Option<&Level> {
+        self.asks.first().filter(|l| !l.is_empty())
+    }
+
+    /// 최우선 매수호가 (비어있으면 None)
+    pub fn best_bid(&self) -> Option<&Level> {
+        self.bids.first().filter(|l| !l.is_empty())
+    }
+
+    /// 스프레드 (최우선 매도호가 - 최우선 매수호가)
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    /// 중간가 ((매도1 + 매수1) / 2)
+    pub fn mid(&self) -> Option<f64> {
+        Some((self.best_ask()?.price + self.best_bid()?.price) / 2.0)
+    }
+
+    /// 1호가 잔량 가중 microprice
+    ///
+    /// `(ask * bid_qty + bid * ask_qty) / (ask_qty + bid_qty)`, 잔량이 모두 0이면 중간가를 돌려줍니다.
+    pub fn microprice(&self, venue: Venue) -> Option<f64> {
+        let ask = self.best_ask()?;
+        let bid = self.best_bid()?;
+        let ask_qty = ask.qty.get(venue) as f64;
+        let bid_qty = bid.qty.get(venue) as f64;
+        let total = ask_qty + bid_qty;
+        if total <= 0.0 {
+            return self.mid();
+        }
+        Some((ask.price * bid_qty + bid.price * ask_qty) / total)
+    }
+
+    /// 단계별 가중 호가 불균형 (-1.0 ~ 1.0, 양수면 매수 우위)
+    ///
+    /// 상위 `depth`단계까지 i번째 단계에 `1 / i` 가중치를 주어
+    /// `(매수 - 매도) / (매수 + 매도)`를 계산합니다. 잔량이 모두 0이면 None입니다.
+    pub fn imbalance(&self, venue: Venue, depth: usize) -> Option<f64> {
+        let depth = depth.min(BOOK_DEPTH);
+        let weighted = |levels: &[Level; BOOK_DEPTH]| -> f64 {
+            levels
+                .iter()
+                .take(depth)
+                .enumerate()
+                .map(|(i, l)| l.qty.get(venue) as f64 / (i + 1) as f64)
+                .sum()
+        };
+        let bid = weighted(&self.bids);
+        let ask = weighted(&self.asks);
+        let total = bid + ask;
+        if total <= 0.0 {
+            return None;
+        }
+        Some((bid - ask) / total)
+    }
+
+    /// 최우선 호가부터 누적한 단계별 잔량
+    pub fn cumulative_depth(&self, side: Side, venue: Venue) -> [i64; BOOK_DEPTH] {
+        let mut acc = 0;
+        let mut out = [0; BOOK_DEPTH];
+        for (slot, level) in out.iter_mut().zip(self.levels(side)) {
+            acc += level.qty.get(venue);
+            *slot = acc;
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_book() -> OrderBook {
+        let mut book = OrderBook::default();
+        for i in 0..BOOK_DEPTH {
+            let step = (i as f64) * 100.0;
+            book.asks[i] = Level::new(71100.0 + step, 100, 50, 150);
+            book.bids[i] = Level::new(71000.0 - step, 300, 0, 300);
+        }
+        book
+    }
+
+    #[test]
+    fn test_from_orderbook_message() {
+        let msg = OrderbookMessage {
+            shcode: "005930".to_string(),
+            offerho1: 71100.0,
+            bidho1: 71000.0,
+            krx_offerrem1: 10,
+            nxt_offerrem1: 5,
+            unt_offerrem1: 15,
+            bidho10: 70100.0,
+            unt_bidrem10: 7,
+            krx_totofferrem: 1000,
+            ..Default::default()
+        };
+        let book = OrderBook::from(&msg);
+        assert_eq!(book.shcode, "005930");
+        assert_eq!(book.asks[0], Level::new(71100.0, 10, 5, 15));
+        assert_eq!(book.bids[0].price, 71000.0);
+        assert_eq!(book.bids[9].price, 70100.0);
+        assert_eq!(book.bids[9].qty.unt, 7);
+        assert_eq!(book.total_ask.krx, 1000);
+    }
+
+    #[test]
+    fn test_spread_mid_microprice() {
+        let book = sample_book();
+        assert_eq!(book.spread(), Some(100.0));
+        assert_eq!(book.mid(), Some(71050.0));
+        // 매수 잔량(300)이 매도 잔량(100)보다 많으므로 매도호가 쪽으로 치우침
+        assert_eq!(book.microprice(Venue::Krx), Some(71075.0));
+        // NXT 매수 잔량이 0이면 매수호가 쪽으로 붙음
+        assert_eq!(book.microprice(Venue::Nxt), Some(71000.0));
+        assert_eq!(OrderBook::default().spread(), None);
+    }
+
+    #[test]
+    fn test_imbalance_and_cumulative_depth() {
+        let book = sample_book();
+        let imb = book.imbalance(Venue::Krx, BOOK_DEPTH).unwrap();
+        assert!((imb - 0.5).abs() < 1e-9);
+        assert_eq!(book.imbalance(Venue::Nxt, 3), Some(-1.0));
+        assert_eq!(OrderBook::default().imbalance(Venue::Unified, 5), None);
+
+        let cum = book.cumulative_depth(Side::Ask, Venue::Unified);
+        assert_eq!(cum[0], 150);
+        assert_eq!(cum[9], 1500);
+        assert_eq!(
+            book.cumulative_depth(Side::Bid, Venue::Nxt),
+            [0; BOOK_DEPTH]
+        );
+    }
+}
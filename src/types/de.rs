@@ -0,0 +1,174 @@
+// LS증권 응답의 숫자 필드 역직렬화 어댑터
+// LS는 대부분의 숫자를 "00071000", "-000123", "  " 같은 문자열로 보내므로
+// 실시간/REST 타입의 숫자 필드에는 `#[serde(deserialize_with = "...")]`로 이 함수들을 지정합니다.
+
+use serde::Deserializer;
+use serde::de::{self, Visitor};
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// 문자열/숫자 양쪽에서 변환 가능한 숫자 타입
+pub trait LsNumber: Sized + Default + FromStr {
+    const EXPECTING: &'static str;
+    fn from_i64(v: i64) -> Option<Self>;
+    fn from_u64(v: u64) -> Option<Self>;
+    fn from_f64(v: f64) -> Option<Self>;
+}
+
+impl LsNumber for i64 {
+    const EXPECTING: &'static str = "정수 또는 정수 문자열";
+    fn from_i64(v: i64) -> Option<Self> {
+        Some(v)
+    }
+    fn from_u64(v: u64) -> Option<Self> {
+        i64::try_from(v).ok()
+    }
+    fn from_f64(v: f64) -> Option<Self> {
+        (v.fract() == 0.0 && v >= i64::MIN as f64 && v <= i64::MAX as f64).then_some(v as i64)
+    }
+}
+
+impl LsNumber for u64 {
+    const EXPECTING: &'static str = "0 이상의 정수 또는 정수 문자열";
+    fn from_i64(v: i64) -> Option<Self> {
+        u64::try_from(v).ok()
+    }
+    fn from_u64(v: u64) -> Option<Self> {
+        Some(v)
+    }
+    fn from_f64(v: f64) -> Option<Self> {
+        (v.fract() == 0.0 && v >= 0.0 && v <= u64::MAX as f64).then_some(v as u64)
+    }
+}
+
+impl LsNumber for f64 {
+    const EXPECTING: &'static str = "실수 또는 실수 문자열";
+    fn from_i64(v: i64) -> Option<Self> {
+        Some(v as f64)
+    }
+    fn from_u64(v: u64) -> Option<Self> {
+        Some(v as f64)
+    }
+    fn from_f64(v: f64) -> Option<Self> {
+        Some(v)
+    }
+}
+
+/// LS 숫자 문자열을 파싱합니다.
+///
+/// - 앞뒤 공백 제거, 빈 문자열/부호만 있는 문자열은 0
+/// - "+000123", "-000123" 같은 부호 + zero-padding 허용
+pub fn parse_ls_number<T: LsNumber>(s: &str) -> Option<T> {
+    let trimmed = s.trim();
+    if trimmed.is_empty() || trimmed == "-" || trimmed == "+" {
+        return Some(T::default());
+    }
+    let unsigned = trimmed.strip_prefix('+').unwrap_or(trimmed);
+    unsigned.parse::<T>().ok()
+}
+
+struct LsNumberVisitor<T>(PhantomData<T>);
+
+impl<T: LsNumber> Visitor<'_> for LsNumberVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(T::EXPECTING)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<T, E> {
+        T::from_i64(v).ok_or_else(|| E::invalid_value(de::Unexpected::Signed(v), &self))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<T, E> {
+        T::from_u64(v).ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<T, E> {
+        T::from_f64(v).ok_or_else(|| E::invalid_value(de::Unexpected::Float(v), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        parse_ls_number(v).ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<T, E> {
+        Ok(T::default())
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<T, E> {
+        Ok(T::default())
+    }
+}
+
+fn deserialize_ls_number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: LsNumber,
+{
+    deserializer.deserialize_any(LsNumberVisitor(PhantomData))
+}
+
+/// 문자열/숫자/빈값(0)을 `f64`로 역직렬화
+pub fn de_f64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    deserialize_ls_number(deserializer)
+}
+
+/// 문자열/숫자/빈값(0)을 `i64`로 역직렬화 ("-000123" 같은 부호 문자열 포함)
+pub fn de_i64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    deserialize_ls_number(deserializer)
+}
+
+/// 문자열/숫자/빈값(0)을 `u64`로 역직렬화
+pub fn de_u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    deserialize_ls_number(deserializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Sample {
+        #[serde(deserialize_with = "de_f64")]
+        price: f64,
+        #[serde(deserialize_with = "de_i64")]
+        qty: i64,
+        #[serde(deserialize_with = "de_u64")]
+        volume: u64,
+    }
+
+    fn parse(json: &str) -> Result<Sample, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    #[test]
+    fn test_zero_padded_and_signed_strings() {
+        let s = parse(r#"{"price":"00071000","qty":"-000123","volume":"+0000042"}"#).unwrap();
+        assert_eq!(s.price, 71000.0);
+        assert_eq!(s.qty, -123);
+        assert_eq!(s.volume, 42);
+
+        let s = parse(r#"{"price":"-0001.25","qty":"+7","volume":"0"}"#).unwrap();
+        assert_eq!(s.price, -1.25);
+        assert_eq!(s.qty, 7);
+    }
+
+    #[test]
+    fn test_blank_as_zero_and_plain_numbers() {
+        let s = parse(r#"{"price":"   ","qty":"","volume":null}"#).unwrap();
+        assert_eq!((s.price, s.qty, s.volume), (0.0, 0, 0));
+
+        let s = parse(r#"{"price":71000,"qty":-5,"volume":10.0}"#).unwrap();
+        assert_eq!((s.price, s.qty, s.volume), (71000.0, -5, 10));
+    }
+
+    #[test]
+    fn test_invalid_values_are_errors() {
+        assert!(parse(r#"{"price":"abc","qty":"1","volume":"1"}"#).is_err());
+        assert!(parse(r#"{"price":"1","qty":"1.5","volume":"1"}"#).is_err());
+        assert!(parse(r#"{"price":"1","qty":"1","volume":"-1"}"#).is_err());
+    }
+}
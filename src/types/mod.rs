@@ -1 +1,7 @@
+pub mod book;
+pub mod de;
 pub mod orderbook;
+pub mod tr_code;
+pub mod tr_key;
+pub mod trade;
+pub mod vi;
@@ -1,3 +1,4 @@
+use crate::types::de::{de_f64, de_i64};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -5,118 +6,248 @@ pub struct OrderbookMessage {
     pub hotime: String, // 호가시간
 
     // 1~10호가
+    #[serde(deserialize_with = "de_f64")]
     pub offerho1: f64,
+    #[serde(deserialize_with = "de_f64")]
     pub bidho1: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_offerrem1: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_offerrem1: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_offerrem1: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_bidrem1: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_bidrem1: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_bidrem1: i64,
 
+    #[serde(deserialize_with = "de_f64")]
     pub offerho2: f64,
+    #[serde(deserialize_with = "de_f64")]
     pub bidho2: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_offerrem2: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_offerrem2: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_offerrem2: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_bidrem2: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_bidrem2: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_bidrem2: i64,
 
+    #[serde(deserialize_with = "de_f64")]
     pub offerho3: f64,
+    #[serde(deserialize_with = "de_f64")]
     pub bidho3: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_offerrem3: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_offerrem3: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_offerrem3: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_bidrem3: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_bidrem3: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_bidrem3: i64,
 
+    #[serde(deserialize_with = "de_f64")]
     pub offerho4: f64,
+    #[serde(deserialize_with = "de_f64")]
     pub bidho4: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_offerrem4: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_offerrem4: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_offerrem4: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_bidrem4: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_bidrem4: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_bidrem4: i64,
 
+    #[serde(deserialize_with = "de_f64")]
     pub offerho5: f64,
+    #[serde(deserialize_with = "de_f64")]
     pub bidho5: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_offerrem5: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_offerrem5: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_offerrem5: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_bidrem5: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_bidrem5: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_bidrem5: i64,
 
+    #[serde(deserialize_with = "de_f64")]
     pub offerho6: f64,
+    #[serde(deserialize_with = "de_f64")]
     pub bidho6: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_offerrem6: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_offerrem6: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_offerrem6: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_bidrem6: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_bidrem6: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_bidrem6: i64,
 
+    #[serde(deserialize_with = "de_f64")]
     pub offerho7: f64,
+    #[serde(deserialize_with = "de_f64")]
     pub bidho7: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_offerrem7: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_offerrem7: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_offerrem7: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_bidrem7: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_bidrem7: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_bidrem7: i64,
 
+    #[serde(deserialize_with = "de_f64")]
     pub offerho8: f64,
+    #[serde(deserialize_with = "de_f64")]
     pub bidho8: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_offerrem8: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_offerrem8: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_offerrem8: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_bidrem8: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_bidrem8: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_bidrem8: i64,
 
+    #[serde(deserialize_with = "de_f64")]
     pub offerho9: f64,
+    #[serde(deserialize_with = "de_f64")]
     pub bidho9: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_offerrem9: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_offerrem9: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_offerrem9: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_bidrem9: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_bidrem9: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_bidrem9: i64,
 
+    #[serde(deserialize_with = "de_f64")]
     pub offerho10: f64,
+    #[serde(deserialize_with = "de_f64")]
     pub bidho10: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_offerrem10: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_offerrem10: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_offerrem10: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_bidrem10: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_bidrem10: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_bidrem10: i64,
 
+    #[serde(deserialize_with = "de_i64")]
     pub krx_totofferrem: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_totofferrem: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_totofferrem: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_totbidrem: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_totbidrem: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub unt_totbidrem: i64,
 
     pub krx_donsigubun: String,
     pub nxt_donsigubun: String,
     pub shcode: String,
     pub alloc_gubun: String,
+    #[serde(deserialize_with = "de_i64")]
     pub volume: i64,
 
+    #[serde(deserialize_with = "de_f64")]
     pub krx_midprice: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_offermidsumrem: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_bidmidsumrem: i64,
+    #[serde(deserialize_with = "de_f64")]
     pub nxt_midprice: f64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_offermidsumrem: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_bidmidsumrem: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_midsumrem: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub krx_midsumremgubun: i64,
+    #[serde(deserialize_with = "de_i64")]
     pub nxt_midsumrem: i64,
     pub nxt_midsumremgubun: String,
     pub ex_shcode: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    /// 모든 숫자 필드를 LS 형식의 zero-padded 문자열로 바꾼 body를 만듭니다.
+    fn ls_encoded_body() -> Value {
+        let mut body = serde_json::to_value(OrderbookMessage::default()).unwrap();
+        for (key, value) in body.as_object_mut().unwrap().iter_mut() {
+            if value.is_number() {
+                *value = Value::String(match key.as_str() {
+                    "offerho1" => "00071100".to_string(),
+                    "bidho1" => "00071000".to_string(),
+                    "krx_offerrem1" => "000000123".to_string(),
+                    "krx_midsumrem" => "-000000045".to_string(),
+                    "krx_midprice" => "        ".to_string(),
+                    _ => "000000000".to_string(),
+                });
+            }
+        }
+        body
+    }
+
+    #[test]
+    fn test_deserialize_ls_string_fields() {
+        let msg: OrderbookMessage = serde_json::from_value(ls_encoded_body()).unwrap();
+        assert_eq!(msg.offerho1, 71100.0);
+        assert_eq!(msg.bidho1, 71000.0);
+        assert_eq!(msg.krx_offerrem1, 123);
+        assert_eq!(msg.krx_midsumrem, -45);
+        assert_eq!(msg.krx_midprice, 0.0);
+    }
+}
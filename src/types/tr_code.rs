@@ -0,0 +1,369 @@
+// LS증권 실시간 시세 TR 코드(tr_cd)와 메타데이터 (시장, 데이터 종류, 계좌 단위 여부)
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// TR이 다루는 시장
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrVenue {
+    /// KOSPI
+    Kospi,
+    /// KOSDAQ
+    Kosdaq,
+    /// KRX 공통 (ETF, 지수, VI, 주문 등 시장 구분 없는 TR)
+    Krx,
+    /// ELW
+    Elw,
+    /// NXT (넥스트레이드)
+    Nxt,
+    /// 통합 (KRX + NXT)
+    Unified,
+}
+
+/// TR이 다루는 데이터 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrCategory {
+    /// 호가잔량
+    Orderbook,
+    /// 우선호가
+    PriorityOrderbook,
+    /// 체결
+    Execution,
+    /// 예상체결
+    ExpectedExecution,
+    /// 기세
+    Trend,
+    /// 거래원
+    Broker,
+    /// 프로그램매매
+    ProgramTrading,
+    /// 투자자 매매추이/현황
+    InvestorTrend,
+    /// VI 발동 해제
+    Vi,
+    /// 상/하한가 진입/이탈
+    PriceLimit,
+    /// 지수/예상지수
+    Index,
+    /// ETF NAV
+    Nav,
+    /// ELW 투자지표 민감도
+    Sensitivity,
+    /// 주문 접수/체결/정정/취소/거부
+    Order,
+    /// 조건검색
+    ConditionSearch,
+}
+
+/// TR별 tr_key 형식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrKeyKind {
+    /// 종목 단축코드 (NXT/통합 TR은 접두어 + 공백 패딩)
+    Symbol,
+    /// 업종코드 (지수/예상지수)
+    Sector,
+    /// 계좌 단위 TR (tr_key 없음)
+    Account,
+    /// 그 외 TR별 고유 형식 (프로그램매매 전체집계, 조건검색 등)
+    Other,
+}
+
+macro_rules! tr_codes {
+    ($( $(#[$doc:meta])* $variant:ident => $code:literal, $venue:ident, $category:ident; )*) => {
+        /// 실시간 시세 TR 코드
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub enum TrCode {
+            $( $(#[$doc])* $variant, )*
+        }
+
+        impl TrCode {
+            /// 정의된 모든 TR 코드
+            pub const ALL: &'static [TrCode] = &[$( TrCode::$variant, )*];
+
+            /// LS API에서 사용하는 tr_cd 문자열
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $( TrCode::$variant => $code, )*
+                }
+            }
+
+            pub fn venue(&self) -> TrVenue {
+                match self {
+                    $( TrCode::$variant => TrVenue::$venue, )*
+                }
+            }
+
+            pub fn category(&self) -> TrCategory {
+                match self {
+                    $( TrCode::$variant => TrCategory::$category, )*
+                }
+            }
+        }
+    };
+}
+
+tr_codes! {
+    //----------[주식 실시간 시세]----------
+    /// ETF 호가잔량
+    EtfOrderbook => "B7_", Krx, Orderbook;
+    /// KOSPI 시간외 단일가 호가잔량
+    KospiAfterHoursOrderbook => "DH1", Kospi, Orderbook;
+    /// KOSDAQ 시간외 단일가 호가잔량
+    KosdaqAfterHoursOrderbook => "DHA", Kosdaq, Orderbook;
+    /// KOSDAQ 시간외 단일가 체결
+    KosdaqAfterHoursExecution => "DK3", Kosdaq, Execution;
+    /// KOSPI 시간외 단일가 체결
+    KospiAfterHoursExecution => "DS3", Kospi, Execution;
+    /// 시간외 단일가 VI 발동 해제
+    AfterHoursViRelease => "DVI", Krx, Vi;
+    /// KOSPI 호가잔량
+    KospiOrderbook => "H1_", Kospi, Orderbook;
+    /// KOSPI 장전 시간외 호가잔량
+    KospiBeforeMarketOrderbook => "H2_", Kospi, Orderbook;
+    /// KOSDAQ 호가잔량
+    KosdaqOrderbook => "HA_", Kosdaq, Orderbook;
+    /// KOSDAQ 장전 시간외 호가잔량
+    KosdaqBeforeMarketOrderbook => "HB_", Kosdaq, Orderbook;
+    /// 코스피 ETF 종목 실시간 NAV
+    KospiEtfNav => "I5_", Kospi, Nav;
+    /// 지수
+    Index => "IJ_", Krx, Index;
+    /// KOSPI 거래원
+    KospiBroker => "K1_", Kospi, Broker;
+    /// KOSDAQ 체결
+    KosdaqExecution => "K3_", Kosdaq, Execution;
+    /// KOSDAQ 프로그램매매 종목별
+    KosdaqProgStock => "KH_", Kosdaq, ProgramTrading;
+    /// KOSDAQ 프로그램매매 전체집계
+    KosdaqProgTotal => "KM_", Kosdaq, ProgramTrading;
+    /// KOSDAQ 우선호가
+    KosdaqPriorityOrderbook => "KS_", Kosdaq, PriorityOrderbook;
+    /// KOSDAQ 거래원
+    KosdaqBroker => "OK_", Kosdaq, Broker;
+    /// KOSPI 프로그램매매 종목별
+    KospiProgStock => "PH_", Kospi, ProgramTrading;
+    /// KOSPI 프로그램매매 전체집계
+    KospiProgTotal => "PM_", Kospi, ProgramTrading;
+    /// KOSPI 우선호가
+    KospiPriorityOrderbook => "S2_", Kospi, PriorityOrderbook;
+    /// KOSPI 체결
+    KospiExecution => "S3_", Kospi, Execution;
+    /// KOSPI 기세
+    KospiTrend => "S4_", Kospi, Trend;
+    /// 주식 주문 접수
+    OrderReceive => "SC0", Krx, Order;
+    /// 주식 주문 체결
+    OrderExecution => "SC1", Krx, Order;
+    /// 주식 주문 정정
+    OrderModify => "SC2", Krx, Order;
+    /// 주식 주문 취소
+    OrderCancel => "SC3", Krx, Order;
+    /// 주식 주문 거부
+    OrderReject => "SC4", Krx, Order;
+    /// 상/하한가 근접 진입
+    LimitNearEnter => "SHC", Krx, PriceLimit;
+    /// 상/하한가 근접 이탈
+    LimitNearLeave => "SHD", Krx, PriceLimit;
+    /// 상/하한가 진입
+    LimitEnter => "SHI", Krx, PriceLimit;
+    /// 상/하한가 이탈
+    LimitLeave => "SHO", Krx, PriceLimit;
+    /// VI 발동 해제
+    ViRelease => "VI_", Krx, Vi;
+    /// 예상지수
+    ExpectedIndex => "YJ_", Krx, Index;
+    /// KOSDAQ 예상 체결
+    KosdaqExpectedExecution => "YK3", Kosdaq, ExpectedExecution;
+    /// KOSPI 예상 체결
+    KospiExpectedExecution => "YS3", Kospi, ExpectedExecution;
+    //----------[ELW]----------
+    /// 뉴 ELW 투자지표 민감도
+    NewElwSensitivity => "ESN", Elw, Sensitivity;
+    /// ELW 장전 시간외 호가잔량
+    ElwBeforeMarketOrderbook => "h2_", Elw, Orderbook;
+    /// ELW 호가잔량
+    ElwOrderbook => "h3_", Elw, Orderbook;
+    /// ELW 거래원
+    ElwBroker => "k1_", Elw, Broker;
+    /// ELW 우선호가
+    ElwPriorityOrderbook => "s2_", Elw, PriorityOrderbook;
+    /// ELW 체결
+    ElwExecution => "s3_", Elw, Execution;
+    /// ELW 기세
+    ElwTrend => "s4_", Elw, Trend;
+    /// ELW 예상 체결
+    ElwExpectedExecution => "Ys3", Elw, ExpectedExecution;
+    //----------[NXT]----------
+    /// (NXT) 체결
+    NxtExecution => "NS3", Nxt, Execution;
+    /// (NXT) 호가잔량
+    NxtOrderbook => "NH1", Nxt, Orderbook;
+    /// (NXT) 우선호가
+    NxtPriorityOrderbook => "NS2", Nxt, PriorityOrderbook;
+    /// (NXT) 예상체결
+    NxtExpectedExecution => "NYS", Nxt, ExpectedExecution;
+    /// (NXT) VI 발동 해제
+    NxtViRelease => "NVI", Nxt, Vi;
+    /// (NXT) 거래원
+    NxtBroker => "NK1", Nxt, Broker;
+    /// (NXT) 프로그램매매 종목별
+    NxtProgStock => "NPH", Nxt, ProgramTrading;
+    /// (NXT) 프로그램매매 전체집계
+    NxtProgTotal => "NPM", Nxt, ProgramTrading;
+    /// (NXT) 시간대별 투자자 매매추이
+    NxtInvestorTrend => "NBT", Nxt, InvestorTrend;
+    /// (NXT) 업종별 투자자별 매매현황
+    NxtSectorInvestorStatus => "NBM", Nxt, InvestorTrend;
+    //----------[통합]----------
+    /// (통합) 체결
+    UniExecution => "US3", Unified, Execution;
+    /// (통합) 호가잔량
+    UniOrderbook => "UH1", Unified, Orderbook;
+    /// (통합) 우선호가
+    UniPriorityOrderbook => "US2", Unified, PriorityOrderbook;
+    /// (통합) 예상체결
+    UniExpectedExecution => "UYS", Unified, ExpectedExecution;
+    /// (통합) 프로그램매매 종목별
+    UniProgStock => "UPH", Unified, ProgramTrading;
+    /// (통합) 거래원
+    UniBroker => "UK1", Unified, Broker;
+    /// (통합) 시간대별 투자자 매매추이
+    UniInvestorTrend => "UBT", Unified, InvestorTrend;
+    /// (통합) 업종별 투자자별 매매현황
+    UniSectorInvestorStatus => "UBM", Unified, InvestorTrend;
+    /// (통합) 프로그램매매 전체집계
+    UniProgTotal => "UPM", Unified, ProgramTrading;
+    /// (통합) VI 발동 해제
+    UniViRelease => "UVI", Unified, Vi;
+    //----------[기타]----------
+    /// API 사용자 조건검색 실시간
+    UserConditionSearch => "AFR", Krx, ConditionSearch;
+}
+
+impl TrCode {
+    /// 계좌 단위로 등록하는 TR(SC0~SC4, 주문 이벤트)인지 여부
+    ///
+    /// 계좌 TR은 tr_type "1"/"2"(계좌 등록/해제)로 구독하며 tr_key에 종목코드를 넣지 않습니다.
+    pub fn is_account_scoped(&self) -> bool {
+        self.category() == TrCategory::Order
+    }
+
+    /// 이 TR이 기대하는 tr_key 형식
+    pub fn key_kind(&self) -> TrKeyKind {
+        match self {
+            TrCode::KosdaqProgTotal
+            | TrCode::KospiProgTotal
+            | TrCode::NxtProgTotal
+            | TrCode::UniProgTotal => TrKeyKind::Other,
+            _ => match self.category() {
+                TrCategory::Order => TrKeyKind::Account,
+                TrCategory::Index => TrKeyKind::Sector,
+                TrCategory::InvestorTrend
+                | TrCategory::PriceLimit
+                | TrCategory::ConditionSearch => TrKeyKind::Other,
+                _ => TrKeyKind::Symbol,
+            },
+        }
+    }
+}
+
+impl fmt::Display for TrCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 알 수 없는 tr_cd 문자열
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTrCodeError(pub String);
+
+impl fmt::Display for ParseTrCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "알 수 없는 tr_cd: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseTrCodeError {}
+
+impl FromStr for TrCode {
+    type Err = ParseTrCodeError;
+
+    /// 대소문자를 구분합니다. ("h2_"는 ELW, "H2_"는 KOSPI)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TrCode::ALL
+            .iter()
+            .copied()
+            .find(|code| code.as_str() == s)
+            .ok_or_else(|| ParseTrCodeError(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for TrCode {
+    type Error = ParseTrCodeError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<TrCode> for String {
+    fn from(code: TrCode) -> Self {
+        code.as_str().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_round_trip_and_unique_codes() {
+        let mut seen = HashSet::new();
+        for code in TrCode::ALL {
+            assert!(seen.insert(code.as_str()), "중복 tr_cd: {}", code);
+            assert_eq!(code.as_str().parse::<TrCode>(), Ok(*code));
+        }
+        assert_eq!("h2_".parse(), Ok(TrCode::ElwBeforeMarketOrderbook));
+        assert_eq!("H2_".parse(), Ok(TrCode::KospiBeforeMarketOrderbook));
+        assert_eq!(
+            "XXX".parse::<TrCode>(),
+            Err(ParseTrCodeError("XXX".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_metadata() {
+        assert_eq!(TrCode::UniOrderbook.venue(), TrVenue::Unified);
+        assert_eq!(TrCode::UniOrderbook.category(), TrCategory::Orderbook);
+        assert_eq!(TrCode::KosdaqExecution.venue(), TrVenue::Kosdaq);
+        assert_eq!(TrCode::ElwExecution.venue(), TrVenue::Elw);
+        assert_eq!(TrCode::NxtViRelease.category(), TrCategory::Vi);
+
+        let account: Vec<&str> = TrCode::ALL
+            .iter()
+            .filter(|c| c.is_account_scoped())
+            .map(|c| c.as_str())
+            .collect();
+        assert_eq!(account, vec!["SC0", "SC1", "SC2", "SC3", "SC4"]);
+
+        assert_eq!(TrCode::UniOrderbook.key_kind(), TrKeyKind::Symbol);
+        assert_eq!(TrCode::Index.key_kind(), TrKeyKind::Sector);
+        assert_eq!(TrCode::OrderExecution.key_kind(), TrKeyKind::Account);
+        assert_eq!(TrCode::KospiProgTotal.key_kind(), TrKeyKind::Other);
+        assert_eq!(TrCode::KospiProgStock.key_kind(), TrKeyKind::Symbol);
+    }
+
+    #[test]
+    fn test_serde_as_string() {
+        assert_eq!(
+            serde_json::to_string(&TrCode::UniExecution).unwrap(),
+            "\"US3\""
+        );
+        let code: TrCode = serde_json::from_str("\"S3_\"").unwrap();
+        assert_eq!(code, TrCode::KospiExecution);
+        assert!(serde_json::from_str::<TrCode>("\"ZZZ\"").is_err());
+    }
+}
@@ -0,0 +1,278 @@
+// 실시간 TR별 tr_key 생성/검증
+// 잘못된 tr_key로 등록해도 서버는 에러 없이 데이터를 보내지 않으므로 등록 전에 형식을 맞춥니다.
+
+use crate::quotation::stock_list::StockItem;
+use crate::types::tr_code::{TrCode, TrKeyKind, TrVenue};
+use crate::websocket::subscription::Subscription;
+use std::fmt;
+
+/// NXT/통합 TR의 tr_key 전체 길이 (접두어 + 단축코드 + 공백 패딩)
+pub const PREFIXED_KEY_LEN: usize = 10;
+/// 종목 단축코드 길이
+pub const SHCODE_LEN: usize = 6;
+/// 업종코드 길이
+pub const SECTOR_CODE_LEN: usize = 3;
+
+/// tr_key 생성 에러
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrKeyError {
+    /// 단축코드가 6자리 영숫자가 아님
+    InvalidShcode(String),
+    /// 업종코드가 3자리 숫자가 아님
+    InvalidSectorCode(String),
+    /// 빈 tr_key
+    Empty(TrCode),
+    /// TR이 요구하는 tr_key 형식과 다른 생성 함수를 사용함
+    KindMismatch {
+        tr_code: TrCode,
+        expected: TrKeyKind,
+        requested: TrKeyKind,
+    },
+    /// NXT 미상장 종목을 NXT TR로 등록하려 함
+    NotNxtListed(String),
+}
+
+impl fmt::Display for TrKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrKeyError::InvalidShcode(code) => {
+                write!(f, "단축코드는 6자리 영숫자여야 합니다: {:?}", code)
+            }
+            TrKeyError::InvalidSectorCode(code) => {
+                write!(f, "업종코드는 3자리 숫자여야 합니다: {:?}", code)
+            }
+            TrKeyError::Empty(tr_code) => write!(f, "{} tr_key가 비어 있습니다", tr_code),
+            TrKeyError::KindMismatch {
+                tr_code,
+                expected,
+                requested,
+            } => write!(
+                f,
+                "{}는 {:?} 형식의 tr_key를 사용합니다 (요청: {:?})",
+                tr_code, expected, requested
+            ),
+            TrKeyError::NotNxtListed(code) => write!(f, "NXT 미상장 종목입니다: {}", code),
+        }
+    }
+}
+
+impl std::error::Error for TrKeyError {}
+
+/// TR 형식에 맞게 만들어진 tr_key
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TrKey {
+    tr_code: TrCode,
+    key: String,
+}
+
+impl TrKey {
+    /// 종목 단축코드로 tr_key를 만듭니다.
+    ///
+    /// - KRX(KOSPI/KOSDAQ/ELW/공통) TR: `"005930"`
+    /// - NXT TR: `"N005930   "`, 통합 TR: `"U005930   "` (10자리 공백 패딩)
+    pub fn for_symbol(tr_code: TrCode, shcode: &str) -> Result<Self, TrKeyError> {
+        ensure_kind(tr_code, TrKeyKind::Symbol)?;
+        let shcode = validate_shcode(shcode)?;
+        let key = match tr_code.venue() {
+            TrVenue::Nxt => prefixed('N', shcode),
+            TrVenue::Unified => prefixed('U', shcode),
+            _ => shcode.to_string(),
+        };
+        Ok(Self { tr_code, key })
+    }
+
+    /// `fetch_stock_list` 결과의 종목으로 tr_key를 만듭니다.
+    ///
+    /// NXT TR은 NXT 상장 종목(`nxt_chk == "1"`)만 허용합니다.
+    /// `fetch_stock_list`가 돌려준 HashMap의 값은 shcode가 비어 있으므로 키를 `shcode`로 넘기세요.
+    pub fn for_stock(tr_code: TrCode, shcode: &str, item: &StockItem) -> Result<Self, TrKeyError> {
+        let shcode = if item.shcode.is_empty() {
+            shcode
+        } else {
+            &item.shcode
+        };
+        if tr_code.venue() == TrVenue::Nxt && item.nxt_chk.trim() != "1" {
+            return Err(TrKeyError::NotNxtListed(shcode.trim().to_string()));
+        }
+        Self::for_symbol(tr_code, shcode)
+    }
+
+    /// 업종코드(3자리)로 지수 TR의 tr_key를 만듭니다.
+    pub fn for_sector(tr_code: TrCode, sector_code: &str) -> Result<Self, TrKeyError> {
+        ensure_kind(tr_code, TrKeyKind::Sector)?;
+        let code = sector_code.trim();
+        if code.len() != SECTOR_CODE_LEN || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(TrKeyError::InvalidSectorCode(sector_code.to_string()));
+        }
+        Ok(Self {
+            tr_code,
+            key: code.to_string(),
+        })
+    }
+
+    /// 계좌 단위 TR(SC0~SC4)의 tr_key (빈 문자열)
+    pub fn account(tr_code: TrCode) -> Result<Self, TrKeyError> {
+        ensure_kind(tr_code, TrKeyKind::Account)?;
+        Ok(Self {
+            tr_code,
+            key: String::new(),
+        })
+    }
+
+    /// TR별 고유 형식의 tr_key를 그대로 사용합니다. (비어 있지 않은지만 확인)
+    pub fn raw(tr_code: TrCode, key: &str) -> Result<Self, TrKeyError> {
+        ensure_kind(tr_code, TrKeyKind::Other)?;
+        if key.trim().is_empty() {
+            return Err(TrKeyError::Empty(tr_code));
+        }
+        Ok(Self {
+            tr_code,
+            key: key.to_string(),
+        })
+    }
+
+    pub fn tr_code(&self) -> TrCode {
+        self.tr_code
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.key
+    }
+
+    pub fn into_string(self) -> String {
+        self.key
+    }
+
+    /// 구독 등록에 사용할 `Subscription`
+    pub fn subscription(&self) -> Subscription {
+        Subscription::new(self.tr_code, self.key.clone())
+    }
+}
+
+impl fmt::Display for TrKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.key)
+    }
+}
+
+fn ensure_kind(tr_code: TrCode, requested: TrKeyKind) -> Result<(), TrKeyError> {
+    let expected = tr_code.key_kind();
+    if expected != requested {
+        return Err(TrKeyError::KindMismatch {
+            tr_code,
+            expected,
+            requested,
+        });
+    }
+    Ok(())
+}
+
+/// 앞에 "A"가 붙은 표준 단축코드("A005930")도 허용합니다.
+fn validate_shcode(shcode: &str) -> Result<&str, TrKeyError> {
+    let trimmed = shcode.trim();
+    let code = match trimmed.strip_prefix('A') {
+        Some(rest) if rest.len() == SHCODE_LEN => rest,
+        _ => trimmed,
+    };
+    if code.len() != SHCODE_LEN || !code.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return Err(TrKeyError::InvalidShcode(shcode.to_string()));
+    }
+    Ok(code)
+}
+
+fn prefixed(prefix: char, shcode: &str) -> String {
+    format!("{}{:<width$}", prefix, shcode, width = PREFIXED_KEY_LEN - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stock(shcode: &str, nxt_chk: &str) -> StockItem {
+        StockItem {
+            hname: "삼성전자".to_string(),
+            shcode: shcode.to_string(),
+            expcode: "KR7005930003".to_string(),
+            etfchk: "0".to_string(),
+            nxt_chk: nxt_chk.to_string(),
+            filler: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_symbol_key_formats() {
+        let key = TrKey::for_symbol(TrCode::UniOrderbook, "005930").unwrap();
+        assert_eq!(key.as_str(), "U005930   ");
+        assert_eq!(
+            TrKey::for_symbol(TrCode::NxtExecution, "A005930")
+                .unwrap()
+                .as_str(),
+            "N005930   "
+        );
+        assert_eq!(
+            TrKey::for_symbol(TrCode::KospiOrderbook, " 005930 ")
+                .unwrap()
+                .as_str(),
+            "005930"
+        );
+        assert_eq!(key.subscription(), Subscription::new("UH1", "U005930   "));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert_eq!(
+            TrKey::for_symbol(TrCode::UniOrderbook, "5930"),
+            Err(TrKeyError::InvalidShcode("5930".to_string()))
+        );
+        assert!(matches!(
+            TrKey::for_symbol(TrCode::Index, "005930"),
+            Err(TrKeyError::KindMismatch {
+                expected: TrKeyKind::Sector,
+                ..
+            })
+        ));
+        assert!(matches!(
+            TrKey::for_symbol(TrCode::OrderExecution, "005930"),
+            Err(TrKeyError::KindMismatch { .. })
+        ));
+        assert_eq!(
+            TrKey::raw(TrCode::KospiProgTotal, " "),
+            Err(TrKeyError::Empty(TrCode::KospiProgTotal))
+        );
+    }
+
+    #[test]
+    fn test_sector_account_and_raw() {
+        assert_eq!(
+            TrKey::for_sector(TrCode::Index, "001").unwrap().as_str(),
+            "001"
+        );
+        assert!(TrKey::for_sector(TrCode::Index, "01").is_err());
+        assert_eq!(TrKey::account(TrCode::OrderReceive).unwrap().as_str(), "");
+        assert_eq!(
+            TrKey::raw(TrCode::KospiProgTotal, "0").unwrap().as_str(),
+            "0"
+        );
+    }
+
+    #[test]
+    fn test_for_stock_checks_nxt_listing() {
+        assert_eq!(
+            TrKey::for_stock(TrCode::NxtOrderbook, "005930", &stock("005930", "0")),
+            Err(TrKeyError::NotNxtListed("005930".to_string()))
+        );
+        assert_eq!(
+            TrKey::for_stock(TrCode::NxtOrderbook, "005930", &stock("005930", "1"))
+                .unwrap()
+                .as_str(),
+            "N005930   "
+        );
+        // fetch_stock_list 결과는 shcode가 HashMap 키로 옮겨져 있음
+        assert_eq!(
+            TrKey::for_stock(TrCode::UniExecution, "000660", &stock("", "0"))
+                .unwrap()
+                .as_str(),
+            "U000660   "
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use crate::types::de::{de_f64, de_i64};
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+/// 체결 방향 (cgubun)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeSide {
+    /// 매수 체결 ("+")
+    Buy,
+    /// 매도 체결 ("-")
+    Sell,
+    /// 구분 없음 (단일가 등)
+    Unknown,
+}
+
+/// 실시간 체결 (S3_: KOSPI, K3_: KOSDAQ, US3: 통합, NS3: NXT)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TradeMessage {
+    pub chetime: String, // 체결시간 (HHMMSS)
+    pub sign: String,    // 전일대비구분 (1:상한 2:상승 3:보합 4:하한 5:하락)
+    #[serde(deserialize_with = "de_f64")]
+    pub change: f64, // 전일대비
+    #[serde(deserialize_with = "de_f64")]
+    pub drate: f64, // 등락율
+    #[serde(deserialize_with = "de_f64")]
+    pub price: f64, // 현재가
+    #[serde(default, deserialize_with = "de_f64")]
+    pub open: f64, // 시가
+    #[serde(default, deserialize_with = "de_f64")]
+    pub high: f64, // 고가
+    #[serde(default, deserialize_with = "de_f64")]
+    pub low: f64, // 저가
+    pub cgubun: String,  // 체결구분 ("+": 매수, "-": 매도)
+    #[serde(deserialize_with = "de_i64")]
+    pub cvolume: i64, // 체결량
+    #[serde(deserialize_with = "de_i64")]
+    pub volume: i64, // 누적거래량
+    #[serde(default, deserialize_with = "de_i64")]
+    pub value: i64, // 누적거래대금 (백만원)
+    #[serde(default, deserialize_with = "de_f64")]
+    pub cpower: f64, // 체결강도
+    #[serde(default, deserialize_with = "de_f64")]
+    pub offerho: f64, // 체결 시점 매도호가
+    #[serde(default, deserialize_with = "de_f64")]
+    pub bidho: f64, // 체결 시점 매수호가
+    pub shcode: String,  // 단축코드
+    #[serde(default)]
+    pub exchname: String, // 거래소명 (US3/NS3)
+}
+
+impl TradeMessage {
+    pub fn side(&self) -> TradeSide {
+        match self.cgubun.trim() {
+            "+" => TradeSide::Buy,
+            "-" => TradeSide::Sell,
+            _ => TradeSide::Unknown,
+        }
+    }
+
+    /// 매수 체결이면 양수, 매도 체결이면 음수인 체결량
+    pub fn signed_volume(&self) -> i64 {
+        match self.side() {
+            TradeSide::Sell => -self.cvolume.abs(),
+            _ => self.cvolume.abs(),
+        }
+    }
+
+    /// 전일대비구분(sign)을 반영한 부호 있는 전일대비
+    pub fn signed_change(&self) -> f64 {
+        match self.sign.trim() {
+            "4" | "5" => -self.change.abs(),
+            "3" => 0.0,
+            _ => self.change.abs(),
+        }
+    }
+
+    /// 체결시간(HHMMSS) 파싱
+    pub fn exec_time(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(self.chetime.trim(), "%H%M%S").ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_us3_body() {
+        let body = r#"{
+            "chetime":"093015","sign":"5","change":"00000500","drate":"-0.70",
+            "price":"00071000","open":"00071500","high":"00071600","low":"00070900",
+            "cgubun":"-","cvolume":"00000010","volume":"000001234567","value":"00087654",
+            "cpower":"00098.50","offerho":"00071100","bidho":"00071000",
+            "shcode":"005930","exchname":"KRX"
+        }"#;
+        let trade: TradeMessage = serde_json::from_str(body).unwrap();
+        assert_eq!(trade.price, 71000.0);
+        assert_eq!(trade.drate, -0.7);
+        assert_eq!(trade.side(), TradeSide::Sell);
+        assert_eq!(trade.signed_volume(), -10);
+        assert_eq!(trade.signed_change(), -500.0);
+        assert_eq!(trade.volume, 1_234_567);
+        assert_eq!(trade.exec_time(), NaiveTime::from_hms_opt(9, 30, 15));
+    }
+
+    #[test]
+    fn test_optional_fields_default() {
+        let body = r#"{"chetime":"","sign":"3","change":"0","drate":"0","price":"100",
+            "cgubun":" ","cvolume":"5","volume":"5","shcode":"000660"}"#;
+        let trade: TradeMessage = serde_json::from_str(body).unwrap();
+        assert_eq!(trade.side(), TradeSide::Unknown);
+        assert_eq!(trade.signed_volume(), 5);
+        assert_eq!(trade.signed_change(), 0.0);
+        assert_eq!(trade.exec_time(), None);
+        assert_eq!(trade.exchname, "");
+    }
+}
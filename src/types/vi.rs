@@ -0,0 +1,80 @@
+use crate::types::de::de_f64;
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+/// VI 발동 구분 (vi_gubun)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViKind {
+    /// 해제 ("0")
+    Released,
+    /// 정적 VI 발동 ("1")
+    Static,
+    /// 동적 VI 발동 ("2")
+    Dynamic,
+    /// 정적 + 동적 VI 발동 ("3")
+    Both,
+    /// 그 외 값
+    Unknown,
+}
+
+/// 실시간 VI 발동 해제 (VI_: KRX, UVI: 통합, NVI: NXT)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ViMessage {
+    pub vi_gubun: String, // 구분 (0:해제 1:정적발동 2:동적발동 3:정적&동적)
+    #[serde(default, deserialize_with = "de_f64")]
+    pub svi_recprice: f64, // 정적VI 발동기준가격
+    #[serde(default, deserialize_with = "de_f64")]
+    pub dvi_recprice: f64, // 동적VI 발동기준가격
+    #[serde(default, deserialize_with = "de_f64")]
+    pub vi_trgprice: f64, // VI 발동가격
+    pub shcode: String,   // 단축코드
+    #[serde(default)]
+    pub ref_shcode: String, // 참조코드
+    #[serde(default)]
+    pub time: String, // 시간 (HHMMSS)
+    #[serde(default)]
+    pub exchname: String, // 거래소명 (UVI/NVI)
+}
+
+impl ViMessage {
+    pub fn kind(&self) -> ViKind {
+        match self.vi_gubun.trim() {
+            "0" => ViKind::Released,
+            "1" => ViKind::Static,
+            "2" => ViKind::Dynamic,
+            "3" => ViKind::Both,
+            _ => ViKind::Unknown,
+        }
+    }
+
+    /// VI가 발동 중인지 여부 (해제 메시지면 false)
+    pub fn is_active(&self) -> bool {
+        matches!(self.kind(), ViKind::Static | ViKind::Dynamic | ViKind::Both)
+    }
+
+    /// 시간(HHMMSS) 파싱
+    pub fn vi_time(&self) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(self.time.trim(), "%H%M%S").ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_vi() {
+        let raw = r#"{"vi_gubun":"2","svi_recprice":"0","dvi_recprice":"70000","vi_trgprice":"72100","shcode":"005930","ref_shcode":"005930","time":"093015","exchname":"KRX"}"#;
+        let vi: ViMessage = serde_json::from_str(raw).unwrap();
+        assert_eq!(vi.kind(), ViKind::Dynamic);
+        assert!(vi.is_active());
+        assert_eq!(vi.dvi_recprice, 70000.0);
+        assert_eq!(vi.vi_trgprice, 72100.0);
+        assert_eq!(vi.vi_time(), NaiveTime::from_hms_opt(9, 30, 15));
+
+        let released: ViMessage =
+            serde_json::from_str(r#"{"vi_gubun":"0","shcode":"005930"}"#).unwrap();
+        assert_eq!(released.kind(), ViKind::Released);
+        assert!(!released.is_active());
+    }
+}
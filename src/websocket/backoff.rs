@@ -0,0 +1,104 @@
+// 재연결 대기시간 계산 (지수 백오프 + jitter + 상한)
+
+use rand::Rng;
+use std::time::Duration;
+
+/// 지수 백오프 상태
+///
+/// n번째 재시도 대기시간은 `min(max, base * multiplier^n)`이며,
+/// 여기에 `jitter` 비율만큼 무작위로 줄인 값을 사용합니다. (여러 프로세스가 동시에 재연결하는 것을 방지)
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    base: Duration,
+    max: Duration,
+    multiplier: f64,
+    jitter: f64,
+    attempt: u32,
+}
+
+impl Backoff {
+    /// `jitter`는 0.0~1.0으로 제한하고, NaN/무한대면 0.0(jitter 없음)으로 봅니다.
+    pub fn new(base: Duration, max: Duration, multiplier: f64, jitter: f64) -> Self {
+        let jitter = if jitter.is_finite() {
+            jitter.clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Self {
+            base,
+            max: max.max(base),
+            multiplier: multiplier.max(1.0),
+            jitter,
+            attempt: 0,
+        }
+    }
+
+    /// 현재까지의 재시도 횟수
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// jitter 적용 전 다음 대기시간 (상한 적용)
+    pub fn current_ceiling(&self) -> Duration {
+        let factor = self
+            .multiplier
+            .powi(self.attempt.min(i32::MAX as u32) as i32);
+        let secs = self.base.as_secs_f64() * factor;
+        if !secs.is_finite() || secs >= self.max.as_secs_f64() {
+            self.max
+        } else {
+            Duration::from_secs_f64(secs)
+        }
+    }
+
+    /// 다음 대기시간을 계산하고 재시도 횟수를 1 증가시킵니다.
+    pub fn next_delay(&mut self) -> Duration {
+        let ceiling = self.current_ceiling();
+        self.attempt = self.attempt.saturating_add(1);
+        if self.jitter == 0.0 {
+            return ceiling;
+        }
+        let cut: f64 = rand::rng().random_range(0.0..=self.jitter);
+        ceiling.mul_f64(1.0 - cut)
+    }
+
+    /// 연결 성공 시 초기 상태로 되돌립니다.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential_growth_with_ceiling() {
+        let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(10), 2.0, 0.0);
+        let delays: Vec<u64> = (0..6).map(|_| backoff.next_delay().as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 10, 10]);
+        assert_eq!(backoff.attempt(), 6);
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_jitter_stays_within_range() {
+        let mut backoff = Backoff::new(Duration::from_secs(4), Duration::from_secs(4), 2.0, 0.5);
+        for _ in 0..100 {
+            let delay = backoff.next_delay();
+            assert!(delay >= Duration::from_secs(2) && delay <= Duration::from_secs(4));
+        }
+    }
+
+    #[test]
+    fn test_non_finite_jitter_is_ignored() {
+        for jitter in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let mut backoff =
+                Backoff::new(Duration::from_secs(1), Duration::from_secs(10), 2.0, jitter);
+            assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+            assert_eq!(backoff.next_delay(), Duration::from_secs(2));
+        }
+    }
+}
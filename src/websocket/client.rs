@@ -1,105 +1,345 @@
 // WebSocket 연결, 재연결, 메시지 수신 등 공통 로직을 처리하는 범용 클라이언트입니다.
 // src/websocket/client.rs
 
+use super::backoff::Backoff;
 use super::handler::{MessageHandler, ParsedMessage};
-use bytes::Bytes;
-use futures::{SinkExt, StreamExt};
-use serde::Serialize;
-use serde_json;
+use super::subscription::{
+    ActiveSubscriptions, Subscription, SubscriptionCommand, SubscriptionHandle,
+};
+use crate::environment::Environment;
+use futures::{Sink, SinkExt, StreamExt, future};
+use serde_json::Value;
 use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
+use tokio::sync::{Mutex, mpsc, watch};
 use tokio::time::sleep;
+use tokio_tungstenite::tungstenite;
 use tokio_tungstenite::{connect_async, tungstenite::Message as TungsteniteMessage};
 
 /// WebSocket 클라이언트의 설정(연결, 재연결, 메시지수신) 등 공통 로직 담당 범용 구조체
 #[derive(Debug, Clone)]
 pub struct ClientConfig {
     pub url: String,
+    /// 재연결 백오프의 최초 대기시간
     pub reconnect_interval: Duration,
+    /// 재연결 백오프 대기시간 상한
+    pub max_reconnect_interval: Duration,
+    /// 재시도마다 대기시간에 곱하는 배수
+    pub backoff_multiplier: f64,
+    /// 대기시간을 무작위로 줄이는 비율 (0.0 ~ 1.0)
+    pub backoff_jitter: f64,
+    /// 연속 일시적 실패(네트워크 에러 등) 허용 횟수
     pub max_reconnect_attempts: usize,
+    /// 연속 치명적 실패(토큰 오류, 잘못된 URL 등) 허용 횟수
+    pub max_fatal_failures: usize,
     pub ping_interval: Duration,
 }
 
+impl ClientConfig {
+    /// 접속 환경의 WebSocket URL을 쓰는 기본 설정
+    pub fn for_environment(environment: &Environment) -> Self {
+        Self {
+            url: environment.ws_url(),
+            ..Self::default()
+        }
+    }
+}
+
 impl Default for ClientConfig {
     fn default() -> Self {
         Self {
-            url: "wss://openapi.ls-sec.co.kr:9443/websocket".to_string(),
+            url: Environment::default().ws_url(),
             reconnect_interval: Duration::from_secs(5),
+            max_reconnect_interval: Duration::from_secs(60),
+            backoff_multiplier: 2.0,
+            backoff_jitter: 0.2,
             max_reconnect_attempts: 10,
+            max_fatal_failures: 3,
             ping_interval: Duration::from_secs(60),
         }
     }
 }
 
+/// 연결 종료 원인
+#[derive(Debug)]
+pub enum ClientError {
+    /// 네트워크 단절 등 재연결로 회복 가능한 에러
+    Transient(Box<dyn Error + Send + Sync>),
+    /// 토큰 오류, 잘못된 URL 등 재연결만으로는 회복되지 않는 에러
+    Fatal(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Transient(e) => write!(f, "일시적 에러: {}", e),
+            ClientError::Fatal(msg) => write!(f, "치명적 에러: {}", msg),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+impl From<tungstenite::Error> for ClientError {
+    fn from(e: tungstenite::Error) -> Self {
+        match &e {
+            tungstenite::Error::Url(_) => ClientError::Fatal(e.to_string()),
+            tungstenite::Error::Http(resp)
+                if matches!(resp.status().as_u16(), 400 | 401 | 403 | 404) =>
+            {
+                ClientError::Fatal(format!("핸드셰이크 거부: HTTP {}", resp.status()))
+            }
+            _ => ClientError::Transient(Box::new(e)),
+        }
+    }
+}
+
+/// 누적 연결/실패 횟수
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReconnectStats {
+    /// 성공한 연결 수
+    pub connects: usize,
+    /// 일시적 실패 누적 횟수
+    pub transient_failures: usize,
+    /// 치명적 실패 누적 횟수
+    pub fatal_failures: usize,
+}
+
 /// 제네릭 `MessageHandler`를 사용하여 WebSocket 통신을 관리하는 클라이언트
 pub struct WebSocketClient<H: MessageHandler> {
     config: ClientConfig,
     handler: H,
+    handle: SubscriptionHandle,
+    active: ActiveSubscriptions,
+    commands: Mutex<mpsc::UnboundedReceiver<SubscriptionCommand>>,
+    seeded: AtomicBool,
+    stats: std::sync::Mutex<ReconnectStats>,
+    token: Option<watch::Receiver<Option<String>>>,
 }
 
 impl<H: MessageHandler + 'static> WebSocketClient<H> {
     pub fn new(config: ClientConfig, handler: H) -> Self {
-        Self { config, handler }
+        let (tx, rx) = mpsc::unbounded_channel();
+        let active = ActiveSubscriptions::default();
+        Self {
+            config,
+            handler,
+            handle: SubscriptionHandle::new(tx, active.clone()),
+            active,
+            commands: Mutex::new(rx),
+            seeded: AtomicBool::new(false),
+            stats: std::sync::Mutex::new(ReconnectStats::default()),
+            token: None,
+        }
+    }
+
+    /// 토큰 교체 알림을 받아, 이후 보내는 요청 header의 `token`을 최신 값으로 바꿉니다.
+    ///
+    /// `TokenManager::subscribe()`의 수신자를 넘기면 재연결/재구독 시 갱신된 토큰을 사용합니다.
+    pub fn with_token_updates(mut self, token: watch::Receiver<Option<String>>) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// 실행 중인 클라이언트에 등록/해제 명령을 보낼 수 있는 핸들
+    pub fn subscription_handle(&self) -> SubscriptionHandle {
+        self.handle.clone()
+    }
+
+    /// 현재 서버에 등록된 구독 목록
+    pub fn active_subscriptions(&self) -> Vec<Subscription> {
+        self.handle.active()
+    }
+
+    /// 누적 연결/실패 횟수
+    pub fn reconnect_stats(&self) -> ReconnectStats {
+        *self.stats.lock().unwrap()
     }
 
     /// 콜백 함수 기반 WebSocket 메시지 처리
     /// 외부에서 FnMut(&H::Message)를 넘기면, 메시지 수신 시마다 콜백을 실행합니다.
+    ///
+    /// 연결이 끊기면 지수 백오프로 재연결하고, 등록돼 있던 구독을 모두 다시 등록합니다.
+    /// 데이터를 정상 수신한 연결이 끊긴 경우에만 백오프와 연속 실패 횟수가 초기화됩니다.
     pub async fn run_with_callback<F>(&self, mut on_msg: F) -> Result<(), Box<dyn Error>>
     where
         F: FnMut(&H::Message) + Send + 'static,
         H::Message: serde::Serialize,
     {
-        let mut reconnect_count = 0;
+        self.run(move |m| {
+            on_msg(&m);
+            future::ready(())
+        })
+        .await
+        .map_err(|e| match e {
+            ClientError::Transient(inner) => inner as Box<dyn Error>,
+            fatal => Box::new(fatal),
+        })
+    }
+
+    /// 비동기 콜백 기반 WebSocket 메시지 처리 (재연결/재구독 포함)
+    ///
+    /// 콜백이 반환한 future가 끝날 때까지 다음 프레임을 읽지 않으므로 콜백 안에서 backpressure를 걸 수 있습니다.
+    pub async fn run<F, Fut>(&self, mut on_msg: F) -> Result<(), ClientError>
+    where
+        F: FnMut(H::Message) -> Fut + Send,
+        Fut: Future<Output = ()> + Send,
+    {
+        let mut backoff = Backoff::new(
+            self.config.reconnect_interval,
+            self.config.max_reconnect_interval,
+            self.config.backoff_multiplier,
+            self.config.backoff_jitter,
+        );
+        let mut transient_count = 0;
+        let mut fatal_count = 0;
         loop {
-            match self.connect_and_listen_with_callback(&mut on_msg).await {
+            let mut healthy = false;
+            let result = self.connect_and_listen(&mut on_msg, &mut healthy).await;
+            if healthy {
+                backoff.reset();
+                transient_count = 0;
+                fatal_count = 0;
+            }
+            match result {
                 Ok(_) => {
                     println!("WebSocket 연결이 정상적으로 종료되었습니다. 재연결을 시도합니다.");
-                    reconnect_count = 0;
                 }
-                Err(e) => {
+                Err(ClientError::Transient(e)) => {
                     eprintln!("WebSocket 에러 발생: {}. 재연결을 시도합니다.", e);
-                    reconnect_count += 1;
-                    if reconnect_count >= self.config.max_reconnect_attempts {
+                    self.stats.lock().unwrap().transient_failures += 1;
+                    transient_count += 1;
+                    if transient_count >= self.config.max_reconnect_attempts {
                         eprintln!("최대 재연결 시도 횟수를 초과했습니다.");
-                        return Err(e);
+                        return Err(ClientError::Transient(e));
+                    }
+                }
+                Err(ClientError::Fatal(msg)) => {
+                    eprintln!("WebSocket 치명적 에러 발생: {}", msg);
+                    self.stats.lock().unwrap().fatal_failures += 1;
+                    fatal_count += 1;
+                    if fatal_count >= self.config.max_fatal_failures {
+                        eprintln!("치명적 에러 허용 횟수를 초과했습니다.");
+                        return Err(ClientError::Fatal(msg));
                     }
                 }
             }
+            let delay = backoff.next_delay();
             println!(
-                "{}초 후 재연결... ({}/{})",
-                self.config.reconnect_interval.as_secs(),
-                reconnect_count,
-                self.config.max_reconnect_attempts
+                "{:.1}초 후 재연결... (일시적 {}/{}, 치명적 {}/{})",
+                delay.as_secs_f64(),
+                transient_count,
+                self.config.max_reconnect_attempts,
+                fatal_count,
+                self.config.max_fatal_failures
             );
-            sleep(self.config.reconnect_interval).await;
+            sleep(delay).await;
+        }
+    }
+
+    /// 보낼 요청 메시지. 토큰 알림을 받고 있으면 header의 token을 최신 값으로 교체합니다.
+    fn outgoing(&self, mut request: Value) -> TungsteniteMessage {
+        if let Some(token) = self.token.as_ref().and_then(|rx| rx.borrow().clone())
+            && let Some(header) = request.get_mut("header").and_then(Value::as_object_mut)
+        {
+            header.insert("token".to_string(), Value::String(token));
+        }
+        TungsteniteMessage::Text(request.to_string())
+    }
+
+    /// 연결 직후 구독 등록
+    ///
+    /// 최초 연결에서는 핸들러의 `subscription_messages()`를 보내고,
+    /// 재연결에서는 active 목록의 구독을 모두 다시 등록합니다.
+    async fn send_subscriptions<W>(&self, write: &mut W) -> Result<(), ClientError>
+    where
+        W: Sink<TungsteniteMessage, Error = tungstenite::Error> + Unpin,
+    {
+        let first_connect = !self.seeded.load(Ordering::SeqCst);
+
+        for initial in self.handler.subscription_messages() {
+            let initial_sub = Subscription::from_request(&initial);
+            if !first_connect && initial_sub.is_some() {
+                continue;
+            }
+            write.send(self.outgoing(initial.clone())).await?;
+            println!("📡 구독 메시지 전송 완료: {}", initial);
+            if let Some(sub) = initial_sub {
+                self.active.lock().unwrap().insert(sub);
+            }
+        }
+
+        if !first_connect {
+            let replay: Vec<Subscription> = self.active.lock().unwrap().iter().cloned().collect();
+            for sub in replay {
+                let request = self
+                    .handler
+                    .request_message(&SubscriptionCommand::Subscribe(sub));
+                write.send(self.outgoing(request.clone())).await?;
+                println!("📡 구독 재등록: {}", request);
+            }
         }
+        self.seeded.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// 런타임 구독 변경 요청 전송
+    ///
+    /// 전송 전에 active 목록부터 갱신하므로, 전송 중 연결이 끊겨도 명령은 재연결 시 반영됩니다.
+    async fn send_command<W>(
+        &self,
+        write: &mut W,
+        command: SubscriptionCommand,
+    ) -> Result<(), ClientError>
+    where
+        W: Sink<TungsteniteMessage, Error = tungstenite::Error> + Unpin,
+    {
+        {
+            let mut active = self.active.lock().unwrap();
+            match &command {
+                SubscriptionCommand::Subscribe(sub) => {
+                    active.insert(sub.clone());
+                }
+                SubscriptionCommand::Unsubscribe(sub) => {
+                    active.remove(sub);
+                }
+            }
+        }
+        let request = self.handler.request_message(&command);
+        write.send(self.outgoing(request.clone())).await?;
+        println!("📡 구독 변경 요청 전송: {}", request);
+        Ok(())
     }
 
     /// 콜백 기반 실제 WebSocket 연결 및 메시지 수신/발신 로직
-    async fn connect_and_listen_with_callback<F>(
+    async fn connect_and_listen<F, Fut>(
         &self,
         on_msg: &mut F,
-    ) -> Result<(), Box<dyn Error>>
+        healthy: &mut bool,
+    ) -> Result<(), ClientError>
     where
-        F: FnMut(&H::Message) + Send + 'static,
-        H::Message: serde::Serialize,
+        F: FnMut(H::Message) -> Fut + Send,
+        Fut: Future<Output = ()> + Send,
     {
         println!("WebSocket에 연결 중... URL: {}", self.config.url);
         let (ws_stream, _) = connect_async(&self.config.url).await?;
         let (mut write, mut read) = ws_stream.split();
         println!("✅ WebSocket 연결 성공!");
+        self.stats.lock().unwrap().connects += 1;
 
-        // 핸들러로부터 구독 메시지를 받아 전송
-        let subscribe_msg = self.handler.subscription_message();
-        write
-            .send(TungsteniteMessage::Text(subscribe_msg.to_string().into()))
-            .await?;
-        println!("📡 구독 메시지 전송 완료: {}", subscribe_msg);
+        self.send_subscriptions(&mut write).await?;
 
         let mut ping_interval = tokio::time::interval(self.config.ping_interval);
+        let mut commands = self.commands.lock().await;
 
         loop {
             tokio::select! {
+                Some(command) = commands.recv() => {
+                    self.send_command(&mut write, command).await?;
+                }
                 _ = ping_interval.tick() => {
                     if let Err(e) = write.send(TungsteniteMessage::Ping(Vec::new())).await {
                         eprintln!("Ping 전송 실패: {}", e);
@@ -108,10 +348,36 @@ impl<H: MessageHandler + 'static> WebSocketClient<H> {
                 }
                 msg = read.next() => {
                     match msg {
-                        Some(Ok(TungsteniteMessage::Binary(binary))) => {
-                            match self.handler.parse_raw_message(&TungsteniteMessage::Binary(binary)) {
+                        Some(Ok(frame @ (TungsteniteMessage::Binary(_) | TungsteniteMessage::Text(_)))) => {
+                            match self.handler.parse_raw_message(&frame) {
                                 ParsedMessage::Message(m) => {
-                                    on_msg(&m); // 콜백 실행
+                                    *healthy = true;
+                                    on_msg(m).await; // 콜백 실행
+                                }
+                                ParsedMessage::Response(header) => {
+                                    if header.is_auth_error() {
+                                        return Err(ClientError::Fatal(format!(
+                                            "인증 실패 응답: rsp_cd={}, rsp_msg={}",
+                                            header.rsp_cd.unwrap_or_default(),
+                                            header.rsp_msg.unwrap_or_default()
+                                        )));
+                                    }
+                                    if header.is_success() {
+                                        *healthy = true;
+                                        println!(
+                                            "📨 서버 응답: tr_cd={}, tr_key={}, {}",
+                                            header.tr_cd,
+                                            header.tr_key,
+                                            header.rsp_msg.unwrap_or_default()
+                                        );
+                                    } else {
+                                        eprintln!(
+                                            "서버 에러 응답: tr_cd={}, rsp_cd={}, rsp_msg={}",
+                                            header.tr_cd,
+                                            header.rsp_cd.unwrap_or_default(),
+                                            header.rsp_msg.unwrap_or_default()
+                                        );
+                                    }
                                 }
                                 ParsedMessage::Pong => {}
                                 ParsedMessage::Ping => {
@@ -122,7 +388,7 @@ impl<H: MessageHandler + 'static> WebSocketClient<H> {
                                     break;
                                 }
                                 ParsedMessage::Unknown => {
-                                    eprintln!("알 수 없는 바이너리 메시지 수신.");
+                                    eprintln!("알 수 없는 메시지 수신.");
                                 }
                             }
                         }
@@ -130,13 +396,10 @@ impl<H: MessageHandler + 'static> WebSocketClient<H> {
                             write.send(TungsteniteMessage::Pong(payload)).await?;
                         }
                         Some(Ok(TungsteniteMessage::Pong(_))) => {}
-                        Some(Ok(TungsteniteMessage::Text(txt))) => {
-                            eprintln!("수신된 텍스트 메시지: {}", txt);
-                        }
                         Some(Ok(_)) => {}
                         Some(Err(e)) => {
                             eprintln!("WebSocket 메시지 수신 에러: {}", e);
-                            return Err(e.into());
+                            return Err(ClientError::Transient(Box::new(e)));
                         }
                         None => {
                             println!("WebSocket 스트림이 종료되었습니다.");
@@ -149,3 +412,219 @@ impl<H: MessageHandler + 'static> WebSocketClient<H> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+    use tokio::net::TcpListener;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Tick {
+        price: String,
+    }
+
+    struct TickHandler;
+
+    impl MessageHandler for TickHandler {
+        type Message = Tick;
+
+        fn subscription_message(&self) -> Value {
+            serde_json::json!({
+                "header": {"token": "test-token", "tr_type": "3"},
+                "body": {"tr_cd": "UH1", "tr_key": "005930"}
+            })
+        }
+    }
+
+    fn test_config(addr: std::net::SocketAddr) -> ClientConfig {
+        ClientConfig {
+            url: format!("ws://{}", addr),
+            reconnect_interval: Duration::from_millis(20),
+            max_reconnect_interval: Duration::from_millis(100),
+            max_reconnect_attempts: 3,
+            max_fatal_failures: 2,
+            ..ClientConfig::default()
+        }
+    }
+
+    async fn recv_json<S>(ws: &mut S) -> Value
+    where
+        S: futures::Stream<Item = Result<TungsteniteMessage, tungstenite::Error>> + Unpin,
+    {
+        loop {
+            if let Some(Ok(TungsteniteMessage::Text(txt))) = ws.next().await {
+                return serde_json::from_str(&txt).unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runtime_subscribe_and_unsubscribe() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = WebSocketClient::new(test_config(addr), TickHandler);
+        let handle = client.subscription_handle();
+
+        let server = async {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+
+            let initial = recv_json(&mut ws).await;
+            assert_eq!(initial["body"]["tr_key"], "005930");
+
+            handle.subscribe("US3", "000660").unwrap();
+            let added = recv_json(&mut ws).await;
+            assert_eq!(added["header"]["tr_type"], "3");
+            assert_eq!(added["header"]["token"], "test-token");
+            assert_eq!(added["body"]["tr_cd"], "US3");
+
+            handle.unsubscribe("UH1", "005930").unwrap();
+            let removed = recv_json(&mut ws).await;
+            assert_eq!(removed["header"]["tr_type"], "4");
+            assert_eq!(removed["body"]["tr_cd"], "UH1");
+
+            // 명령 처리 후 active 목록 갱신 확인
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert_eq!(handle.active(), vec![Subscription::new("US3", "000660")]);
+        };
+
+        tokio::select! {
+            _ = server => {}
+            res = client.run_with_callback(|_: &Tick| {}) => panic!("클라이언트가 먼저 종료됨: {:?}", res.err().map(|e| e.to_string())),
+        }
+        assert!(
+            client
+                .active_subscriptions()
+                .contains(&Subscription::new("US3", "000660"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_replays_active_subscriptions() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = WebSocketClient::new(test_config(addr), TickHandler);
+        let handle = client.subscription_handle();
+
+        let server = async {
+            // 1차 연결: 최초 구독 + 런타임 구독 후 연결 끊기
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            recv_json(&mut ws).await;
+            handle.subscribe("US3", "000660").unwrap();
+            recv_json(&mut ws).await;
+            drop(ws);
+
+            // 2차 연결: active 목록 전체가 재등록되어야 함
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            let mut replayed = vec![
+                recv_json(&mut ws).await["body"]["tr_cd"].clone(),
+                recv_json(&mut ws).await["body"]["tr_cd"].clone(),
+            ];
+            replayed.sort_by_key(|v| v.to_string());
+            assert_eq!(replayed, vec!["UH1", "US3"]);
+        };
+
+        tokio::select! {
+            _ = server => {}
+            res = client.run_with_callback(|_: &Tick| {}) => panic!("클라이언트가 먼저 종료됨: {:?}", res.err().map(|e| e.to_string())),
+        }
+        assert_eq!(client.reconnect_stats().connects, 2);
+    }
+
+    /// 명령 전송 도중 연결이 끊긴 sink
+    fn dropped_connection() -> impl Sink<TungsteniteMessage, Error = tungstenite::Error> + Unpin {
+        Box::pin(futures::sink::unfold(
+            (),
+            |_, _: TungsteniteMessage| async { Err(tungstenite::Error::ConnectionClosed) },
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_command_kept_when_connection_drops_mid_send() {
+        let addr = "127.0.0.1:9".parse().unwrap();
+        let client = WebSocketClient::new(test_config(addr), TickHandler);
+
+        // 전송에 실패해도 재연결 시 재등록되도록 active 목록에 남아야 함
+        let subscribe = SubscriptionCommand::Subscribe(Subscription::new("US3", "000660"));
+        let result = client
+            .send_command(&mut dropped_connection(), subscribe)
+            .await;
+        assert!(result.is_err());
+        assert_eq!(
+            client.active_subscriptions(),
+            vec![Subscription::new("US3", "000660")]
+        );
+
+        let unsubscribe = SubscriptionCommand::Unsubscribe(Subscription::new("US3", "000660"));
+        let result = client
+            .send_command(&mut dropped_connection(), unsubscribe)
+            .await;
+        assert!(result.is_err());
+        assert!(client.active_subscriptions().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rotated_token_used_after_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let (token_tx, token_rx) = watch::channel(Some("tok-1".to_string()));
+        let client =
+            WebSocketClient::new(test_config(addr), TickHandler).with_token_updates(token_rx);
+
+        let server = async {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            assert_eq!(recv_json(&mut ws).await["header"]["token"], "tok-1");
+
+            // 토큰 교체 후 연결이 끊기면 재등록 요청에 새 토큰이 실려야 함
+            token_tx.send_replace(Some("tok-2".to_string()));
+            drop(ws);
+
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            assert_eq!(recv_json(&mut ws).await["header"]["token"], "tok-2");
+        };
+
+        tokio::select! {
+            _ = server => {}
+            res = client.run_with_callback(|_: &Tick| {}) => panic!("클라이언트가 먼저 종료됨: {:?}", res.err().map(|e| e.to_string())),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_auth_error_is_fatal() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let client = WebSocketClient::new(test_config(addr), TickHandler);
+
+        let server = async {
+            loop {
+                let (tcp, _) = listener.accept().await.unwrap();
+                let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+                recv_json(&mut ws).await;
+                let reply = r#"{"header":{"tr_cd":"UH1","tr_key":"005930","rsp_cd":"IGW00121","rsp_msg":"유효하지 않은 토큰입니다"},"body":null}"#;
+                ws.send(TungsteniteMessage::Text(reply.to_string()))
+                    .await
+                    .unwrap();
+            }
+        };
+
+        let result = tokio::select! {
+            _ = server => unreachable!(),
+            res = client.run_with_callback(|_: &Tick| {}) => res,
+        };
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("치명적"), "{}", err);
+        let stats = client.reconnect_stats();
+        assert_eq!(stats.fatal_failures, 2);
+        assert_eq!(stats.transient_failures, 0);
+    }
+}
@@ -0,0 +1,269 @@
+// 하나의 WebSocket 연결에서 여러 TR(호가, 체결, VI 등)을 받아
+// envelope의 tr_cd 기준으로 타입별 디코더에 라우팅하는 다중화 핸들러
+
+use super::handler::{LsHeader, MessageHandler, ParsedMessage, parse_envelope_with};
+use super::subscription::{Subscription, SubscriptionCommand};
+use crate::constant::LS_WS_TR_TYPE_REGISTER;
+use crate::types::orderbook::OrderbookMessage;
+use crate::types::tr_code::TrCode;
+use crate::types::trade::TradeMessage;
+use crate::types::vi::ViMessage;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use tokio_tungstenite::tungstenite::Message as TungsteniteMessage;
+
+/// 디스패처가 내보내는 실시간 메시지
+///
+/// 등록된 tr_cd는 타입이 지정된 variant로, 등록되지 않은 tr_cd는 `Other`로 전달됩니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RealtimeMessage {
+    /// 호가잔량
+    Orderbook(Box<OrderbookMessage>),
+    /// 체결
+    Trade(Box<TradeMessage>),
+    /// VI 발동 해제
+    Vi(Box<ViMessage>),
+    /// 디코더가 등록되지 않은 TR (header + 원본 body)
+    Other { header: LsHeader, body: Value },
+}
+
+impl From<OrderbookMessage> for RealtimeMessage {
+    fn from(msg: OrderbookMessage) -> Self {
+        RealtimeMessage::Orderbook(Box::new(msg))
+    }
+}
+
+impl From<TradeMessage> for RealtimeMessage {
+    fn from(msg: TradeMessage) -> Self {
+        RealtimeMessage::Trade(Box::new(msg))
+    }
+}
+
+impl From<ViMessage> for RealtimeMessage {
+    fn from(msg: ViMessage) -> Self {
+        RealtimeMessage::Vi(Box::new(msg))
+    }
+}
+
+type Decoder = Box<dyn Fn(Value) -> Result<RealtimeMessage, serde_json::Error> + Send + Sync>;
+
+/// tr_cd 기준 라우팅 핸들러
+///
+/// `WebSocketClient<Dispatcher>`로 사용하면 하나의 연결에서 여러 TR을 구독하고
+/// `RealtimeMessage` 하나의 타입으로 받을 수 있습니다.
+pub struct Dispatcher {
+    token: String,
+    subscriptions: Vec<Subscription>,
+    routes: HashMap<TrCode, Decoder>,
+}
+
+impl Dispatcher {
+    /// 디코더가 하나도 없는 디스패처 (모든 프레임이 `Other`로 전달됨)
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            subscriptions: Vec::new(),
+            routes: HashMap::new(),
+        }
+    }
+
+    /// 기본 디코더가 등록된 디스패처
+    ///
+    /// - `UH1`(통합 호가잔량) → `RealtimeMessage::Orderbook`
+    /// - `S3_`/`K3_`/`US3`/`NS3`(체결) → `RealtimeMessage::Trade`
+    /// - `VI_`/`UVI`/`NVI`(VI 발동 해제) → `RealtimeMessage::Vi`
+    pub fn with_default_routes(token: impl Into<String>) -> Self {
+        Self::new(token)
+            .route::<OrderbookMessage>(TrCode::UniOrderbook)
+            .route::<TradeMessage>(TrCode::KospiExecution)
+            .route::<TradeMessage>(TrCode::KosdaqExecution)
+            .route::<TradeMessage>(TrCode::UniExecution)
+            .route::<TradeMessage>(TrCode::NxtExecution)
+            .route::<ViMessage>(TrCode::ViRelease)
+            .route::<ViMessage>(TrCode::UniViRelease)
+            .route::<ViMessage>(TrCode::NxtViRelease)
+    }
+
+    /// tr_cd에 body 타입 `T`를 등록합니다. 같은 tr_cd를 다시 등록하면 덮어씁니다.
+    pub fn route<T>(mut self, tr_cd: TrCode) -> Self
+    where
+        T: DeserializeOwned + Into<RealtimeMessage> + 'static,
+    {
+        self.routes.insert(
+            tr_cd,
+            Box::new(|body| serde_json::from_value::<T>(body).map(Into::into)),
+        );
+        self
+    }
+
+    /// 최초 연결 시 등록할 구독을 추가합니다.
+    pub fn subscribe(mut self, tr_cd: impl Into<String>, tr_key: impl Into<String>) -> Self {
+        self.subscriptions.push(Subscription::new(tr_cd, tr_key));
+        self
+    }
+
+    /// 디코더가 등록된 tr_cd 목록
+    pub fn routed_tr_codes(&self) -> Vec<TrCode> {
+        let mut codes: Vec<TrCode> = self.routes.keys().copied().collect();
+        codes.sort_unstable_by_key(|code| code.as_str());
+        codes
+    }
+
+    fn request(&self, tr_type: &str, sub: &Subscription) -> Value {
+        serde_json::json!({
+            "header": {
+                "token": self.token,
+                "tr_type": tr_type,
+            },
+            "body": {
+                "tr_cd": sub.tr_cd,
+                "tr_key": sub.tr_key,
+            }
+        })
+    }
+
+    /// envelope를 파싱하여 tr_cd에 맞는 디코더로 body를 변환합니다.
+    pub fn dispatch(&self, raw: &[u8]) -> ParsedMessage<RealtimeMessage> {
+        parse_envelope_with(raw, |header, body| {
+            let decoder = header
+                .tr_cd
+                .parse::<TrCode>()
+                .ok()
+                .and_then(|code| self.routes.get(&code));
+            match decoder {
+                Some(decode) => decode(body),
+                None => Ok(RealtimeMessage::Other {
+                    header: header.clone(),
+                    body,
+                }),
+            }
+        })
+    }
+}
+
+impl MessageHandler for Dispatcher {
+    type Message = RealtimeMessage;
+
+    fn subscription_message(&self) -> Value {
+        match self.subscriptions.first() {
+            Some(sub) => self.request(LS_WS_TR_TYPE_REGISTER, sub),
+            None => self.request(LS_WS_TR_TYPE_REGISTER, &Subscription::new("", "")),
+        }
+    }
+
+    fn subscription_messages(&self) -> Vec<Value> {
+        self.subscriptions
+            .iter()
+            .map(|sub| self.request(LS_WS_TR_TYPE_REGISTER, sub))
+            .collect()
+    }
+
+    fn request_message(&self, command: &SubscriptionCommand) -> Value {
+        self.request(command.tr_type(), command.subscription())
+    }
+
+    fn parse_raw_message(&self, msg: &TungsteniteMessage) -> ParsedMessage<RealtimeMessage> {
+        match msg {
+            TungsteniteMessage::Binary(bin) => self.dispatch(bin),
+            TungsteniteMessage::Text(text) => self.dispatch(text.as_bytes()),
+            TungsteniteMessage::Ping(_) => ParsedMessage::Ping,
+            TungsteniteMessage::Pong(_) => ParsedMessage::Pong,
+            TungsteniteMessage::Close(_) => ParsedMessage::Closed,
+            _ => ParsedMessage::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn orderbook_frame() -> String {
+        let mut body = serde_json::to_value(OrderbookMessage::default()).unwrap();
+        body["shcode"] = Value::from("005930");
+        serde_json::json!({
+            "header": {"tr_cd": "UH1", "tr_key": "005930"},
+            "body": body,
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_routes_by_tr_cd() {
+        let dispatcher = Dispatcher::with_default_routes("t");
+        match dispatcher.dispatch(orderbook_frame().as_bytes()) {
+            ParsedMessage::Message(RealtimeMessage::Orderbook(book)) => {
+                assert_eq!(book.shcode, "005930")
+            }
+            other => panic!("Orderbook이 아님: {:?}", other),
+        }
+
+        let trade = r#"{"header":{"tr_cd":"S3_","tr_key":"005930"},"body":{"chetime":"090000","sign":"2","change":"100","drate":"0.14","price":"71000","cgubun":"+","cvolume":"3","volume":"3","shcode":"005930"}}"#;
+        match dispatcher.dispatch(trade.as_bytes()) {
+            ParsedMessage::Message(RealtimeMessage::Trade(trade)) => {
+                assert_eq!(trade.signed_volume(), 3)
+            }
+            other => panic!("Trade가 아님: {:?}", other),
+        }
+
+        let vi = r#"{"header":{"tr_cd":"UVI","tr_key":"005930"},"body":{"vi_gubun":"1","shcode":"005930"}}"#;
+        match dispatcher.dispatch(vi.as_bytes()) {
+            ParsedMessage::Message(RealtimeMessage::Vi(vi)) => assert!(vi.is_active()),
+            other => panic!("Vi가 아님: {:?}", other),
+        }
+
+        let program = r#"{"header":{"tr_cd":"UPM","tr_key":"1"},"body":{"tjjcode":"0001"}}"#;
+        match dispatcher.dispatch(program.as_bytes()) {
+            ParsedMessage::Message(RealtimeMessage::Other { header, body }) => {
+                assert_eq!(header.tr_cd, "UPM");
+                assert_eq!(body["tjjcode"], "0001");
+            }
+            other => panic!("Other가 아님: {:?}", other),
+        }
+
+        // 디코더는 있지만 body가 맞지 않으면 Unknown
+        let broken = r#"{"header":{"tr_cd":"UVI","tr_key":"005930"},"body":{"shcode":1}}"#;
+        assert!(matches!(
+            dispatcher.dispatch(broken.as_bytes()),
+            ParsedMessage::Unknown
+        ));
+
+        let ack = r#"{"header":{"tr_cd":"UH1","tr_key":"005930","rsp_cd":"00000"},"body":null}"#;
+        assert!(matches!(
+            dispatcher.dispatch(ack.as_bytes()),
+            ParsedMessage::Response(_)
+        ));
+    }
+
+    #[test]
+    fn test_subscription_messages() {
+        let dispatcher = Dispatcher::with_default_routes("abc")
+            .subscribe(TrCode::UniOrderbook, "005930")
+            .subscribe(TrCode::UniExecution, "005930");
+        let messages = dispatcher.subscription_messages();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[1]["header"]["token"], "abc");
+        assert_eq!(messages[1]["body"]["tr_cd"], "US3");
+        assert_eq!(
+            dispatcher.routed_tr_codes(),
+            vec![
+                TrCode::KosdaqExecution,
+                TrCode::NxtExecution,
+                TrCode::NxtViRelease,
+                TrCode::KospiExecution,
+                TrCode::UniOrderbook,
+                TrCode::UniExecution,
+                TrCode::UniViRelease,
+                TrCode::ViRelease,
+            ]
+        );
+
+        let request = dispatcher.request_message(&SubscriptionCommand::Unsubscribe(
+            Subscription::new("UVI", "005930"),
+        ));
+        assert_eq!(request["header"]["tr_type"], "4");
+        assert_eq!(request["body"]["tr_cd"], "UVI");
+    }
+}
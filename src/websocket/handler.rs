@@ -1,17 +1,65 @@
 // WebSocket 메시지 처리를 위한 공통 트레이트와 타입을 정의합니다.
 // 이 코드에서는 ws 메시지 parsing과 handler trait(message handler) 정의되어있음음
 
-use serde::Serialize;
+use super::subscription::SubscriptionCommand;
+use crate::constant::{LS_RSP_CD_AUTH, LS_RSP_CD_OK};
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fmt::Debug;
 use tokio_tungstenite::tungstenite::Message as TungsteniteMessage;
 
+/// LS 실시간 프레임의 header 부분
+///
+/// 실시간 데이터 프레임에는 `tr_cd`/`tr_key`만, 등록/해제 응답 프레임에는
+/// `tr_type`/`rsp_cd`/`rsp_msg`가 함께 들어옵니다.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LsHeader {
+    #[serde(default)]
+    pub tr_cd: String,
+    #[serde(default)]
+    pub tr_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tr_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rsp_cd: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rsp_msg: Option<String>,
+}
+
+impl LsHeader {
+    /// `rsp_cd`가 없거나 "00000"이면 정상으로 간주합니다.
+    pub fn is_success(&self) -> bool {
+        self.rsp_cd.as_deref().is_none_or(|cd| cd == LS_RSP_CD_OK)
+    }
+
+    /// 토큰/인증 관련 에러 응답인지 여부 (`LS_RSP_CD_AUTH`의 rsp_cd)
+    ///
+    /// 재연결해도 같은 결과가 나오므로 클라이언트는 이 응답을 받으면 재연결하지 않고 종료합니다.
+    pub fn is_auth_error(&self) -> bool {
+        self.rsp_cd
+            .as_deref()
+            .is_some_and(|cd| LS_RSP_CD_AUTH.contains(&cd))
+    }
+}
+
+/// LS 실시간 프레임 `{"header": {...}, "body": {...}}`
+///
+/// 등록/해제 응답은 `body`가 null이거나 빠져 있으므로 `Option`으로 받습니다.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LsEnvelope<B> {
+    pub header: LsHeader,
+    #[serde(default)]
+    pub body: Option<B>,
+}
+
 /// 파싱된 WebSocket 메시지의 종류
 #[derive(Debug)]
 pub enum ParsedMessage<T> {
     /// 성공적으로 파싱된 데이터 메시지
     Message(T),
+    /// 등록/해제 요청에 대한 서버 응답 (body 없음)
+    Response(LsHeader),
     /// Pong 메시지
     Pong,
     /// Ping 메시지
@@ -22,42 +70,86 @@ pub enum ParsedMessage<T> {
     Unknown,
 }
 
+/// 원시 프레임(JSON 바이트)을 envelope 기준으로 파싱합니다.
+///
+/// body가 있으면 `T`로 역직렬화하고, body가 없으면 header만 `Response`로 돌려줍니다.
+pub fn parse_envelope<T: DeserializeOwned>(raw: &[u8]) -> ParsedMessage<T> {
+    parse_envelope_with(raw, |_, body| serde_json::from_value::<T>(body))
+}
+
+/// `parse_envelope`와 같지만 body 변환을 `decode`에 맡깁니다.
+///
+/// tr_cd에 따라 body 타입이 달라지는 핸들러(`Dispatcher`)가 header를 보고 디코더를 고를 때 씁니다.
+pub fn parse_envelope_with<T, F>(raw: &[u8], decode: F) -> ParsedMessage<T>
+where
+    F: FnOnce(&LsHeader, Value) -> Result<T, serde_json::Error>,
+{
+    let envelope = match serde_json::from_slice::<LsEnvelope<Value>>(raw) {
+        Ok(env) => env,
+        Err(e) => {
+            eprintln!(
+                "envelope 파싱 실패: {} / 원문: {}",
+                e,
+                String::from_utf8_lossy(raw)
+            );
+            return ParsedMessage::Unknown;
+        }
+    };
+
+    match envelope.body {
+        None | Some(Value::Null) => ParsedMessage::Response(envelope.header),
+        Some(body) => match decode(&envelope.header, body) {
+            Ok(msg) => ParsedMessage::Message(msg),
+            Err(e) => {
+                eprintln!(
+                    "body 파싱 실패 (tr_cd={}, tr_key={}): {}",
+                    envelope.header.tr_cd, envelope.header.tr_key, e
+                );
+                ParsedMessage::Unknown
+            }
+        },
+    }
+}
+
 /// WebSocket 메시지를 처리하는 핸들러의 동작을 정의하는 트레이트
 ///
 /// 이 트레이트를 구현하여 특정 스트림(Ticker, Trade 등)에 대한
 /// 구독 메시지 생성 및 데이터 파싱 로직을 정의할 수 있습니다.
 pub trait MessageHandler: Send + Sync {
-    /// 핸들러가 처리할 메시지의 타입
+    /// 핸들러가 처리할 메시지의 타입 (envelope의 body 부분)
     type Message: DeserializeOwned + Debug + Send + Serialize;
 
     /// WebSocket 구독을 위한 메시지를 생성합니다.
     fn subscription_message(&self) -> Value;
 
+    /// 최초 연결 시 보낼 구독 메시지 목록 (기본: `subscription_message()` 하나)
+    fn subscription_messages(&self) -> Vec<Value> {
+        vec![self.subscription_message()]
+    }
+
+    /// 런타임 등록/해제 명령을 요청 메시지로 변환합니다.
+    ///
+    /// 기본 구현은 `subscription_message()`의 header(token 등)를 재사용하고
+    /// `tr_type`과 body의 `tr_cd`/`tr_key`만 교체합니다.
+    fn request_message(&self, command: &SubscriptionCommand) -> Value {
+        let mut request = self.subscription_message();
+        let sub = command.subscription();
+        request["header"]["tr_type"] = Value::from(command.tr_type());
+        request["body"] = serde_json::json!({
+            "tr_cd": sub.tr_cd,
+            "tr_key": sub.tr_key,
+        });
+        request
+    }
+
     /// 원시 WebSocket 메시지(TungsteniteMessage)를 파싱하여
     /// 애플리케이션에서 사용할 수 있는 `ParsedMessage`로 변환합니다.
+    ///
+    /// LS 서버는 데이터를 Text/Binary 프레임 모두로 보낼 수 있으므로 둘 다 envelope로 파싱합니다.
     fn parse_raw_message(&self, msg: &TungsteniteMessage) -> ParsedMessage<Self::Message> {
         match msg {
-            TungsteniteMessage::Binary(bin) => {
-                // 데이터 메시지로 파싱 시도
-                if let Ok(json) = serde_json::from_slice::<Self::Message>(bin) {
-                    return ParsedMessage::Message(json);
-                }
-                // 에러 구조가 없으므로, 그냥 사람이 볼 수 있게 출력
-                match String::from_utf8(bin.to_vec()) {
-                    Ok(decoded) => {
-                        eprintln!("수신된 바이너리(텍스트): {}", decoded);
-                    }
-                    Err(e) => {
-                        eprintln!("UTF-8 디코딩 실패: {}", e);
-                    }
-                }
-                ParsedMessage::Unknown
-            }
-            TungsteniteMessage::Text(text) => {
-                // 그냥 로그로 남기고 Unknown 처리
-                eprintln!("수신된 텍스트 메시지: {}", text);
-                ParsedMessage::Unknown
-            }
+            TungsteniteMessage::Binary(bin) => parse_envelope(bin),
+            TungsteniteMessage::Text(text) => parse_envelope(text.as_bytes()),
             TungsteniteMessage::Ping(_) => ParsedMessage::Ping,
             TungsteniteMessage::Pong(_) => ParsedMessage::Pong,
             TungsteniteMessage::Close(_) => ParsedMessage::Closed,
@@ -65,3 +157,107 @@ pub trait MessageHandler: Send + Sync {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::subscription::Subscription;
+
+    #[derive(Debug, Deserialize, Serialize, PartialEq)]
+    struct Tick {
+        price: String,
+    }
+
+    struct TickHandler;
+
+    impl MessageHandler for TickHandler {
+        type Message = Tick;
+
+        fn subscription_message(&self) -> Value {
+            Value::Null
+        }
+    }
+
+    #[test]
+    fn test_parse_data_frame_text_and_binary() {
+        let raw = r#"{"header":{"tr_cd":"UH1","tr_key":"005930"},"body":{"price":"00071000"}}"#;
+        let handler = TickHandler;
+
+        for msg in [
+            TungsteniteMessage::Text(raw.into()),
+            TungsteniteMessage::Binary(raw.as_bytes().to_vec()),
+        ] {
+            match handler.parse_raw_message(&msg) {
+                ParsedMessage::Message(tick) => assert_eq!(tick.price, "00071000"),
+                other => panic!("Message가 아님: {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_response_frame() {
+        let raw = r#"{"header":{"tr_cd":"UH1","tr_key":"005930","tr_type":"3","rsp_cd":"00000","rsp_msg":"정상처리되었습니다"},"body":null}"#;
+        match parse_envelope::<Tick>(raw.as_bytes()) {
+            ParsedMessage::Response(header) => {
+                assert_eq!(header.tr_cd, "UH1");
+                assert_eq!(header.tr_type.as_deref(), Some("3"));
+                assert!(header.is_success());
+            }
+            other => panic!("Response가 아님: {:?}", other),
+        }
+
+        let raw =
+            r#"{"header":{"tr_cd":"UH1","tr_key":"","rsp_cd":"IGW00121","rsp_msg":"토큰 오류"}}"#;
+        match parse_envelope::<Tick>(raw.as_bytes()) {
+            ParsedMessage::Response(header) => {
+                assert!(!header.is_success());
+                assert!(header.is_auth_error());
+            }
+            other => panic!("Response가 아님: {:?}", other),
+        }
+
+        // 메시지에 "토큰"이 있어도 rsp_cd가 인증 오류 코드가 아니면 인증 실패가 아님
+        let raw = r#"{"header":{"tr_cd":"UH1","tr_key":"","rsp_cd":"IGW00999","rsp_msg":"토큰 처리 지연"}}"#;
+        match parse_envelope::<Tick>(raw.as_bytes()) {
+            ParsedMessage::Response(header) => {
+                assert!(!header.is_success());
+                assert!(!header.is_auth_error());
+            }
+            other => panic!("Response가 아님: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_default_request_message() {
+        struct TokenHandler;
+        impl MessageHandler for TokenHandler {
+            type Message = Tick;
+            fn subscription_message(&self) -> Value {
+                serde_json::json!({
+                    "header": {"token": "abc", "tr_type": "3"},
+                    "body": {"tr_cd": "UH1", "tr_key": "005930"}
+                })
+            }
+        }
+
+        let command = SubscriptionCommand::Unsubscribe(Subscription::new("US3", "000660"));
+        let request = TokenHandler.request_message(&command);
+        assert_eq!(request["header"]["token"], "abc");
+        assert_eq!(request["header"]["tr_type"], "4");
+        assert_eq!(request["body"]["tr_cd"], "US3");
+        assert_eq!(request["body"]["tr_key"], "000660");
+    }
+
+    #[test]
+    fn test_parse_invalid_frame() {
+        assert!(matches!(
+            parse_envelope::<Tick>(b"not json"),
+            ParsedMessage::Unknown
+        ));
+        let raw = r#"{"header":{"tr_cd":"UH1","tr_key":"005930"},"body":{"volume":1}}"#;
+        assert!(matches!(
+            parse_envelope::<Tick>(raw.as_bytes()),
+            ParsedMessage::Unknown
+        ));
+    }
+}
@@ -1,3 +1,8 @@
+pub mod backoff;
 pub mod client;
+pub mod dispatcher;
 pub mod handler;
+pub mod stream;
+pub mod subscription;
 pub mod ws_orderbook_total;
+pub mod ws_trade;
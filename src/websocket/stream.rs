@@ -0,0 +1,441 @@
+// WebSocketClient를 futures::Stream으로 소비하기 위한 bounded 채널과 backpressure 정책
+
+use super::client::{ClientError, WebSocketClient};
+use super::handler::MessageHandler;
+use futures::Stream;
+use futures::stream::BoxStream;
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+/// 버퍼가 가득 찼을 때의 처리 방식
+pub enum BackpressurePolicy<T> {
+    /// 가장 오래된 메시지를 버리고 새 메시지를 넣습니다.
+    DropOldest,
+    /// 소비자가 메시지를 꺼낼 때까지 수신을 멈춥니다. (WebSocket 읽기도 함께 멈춤)
+    Block,
+    /// 같은 키(예: 종목코드)의 메시지는 최신 값 하나만 유지합니다.
+    /// 서로 다른 키가 버퍼보다 많으면 가장 오래된 키를 버립니다.
+    ConflateByKey(Arc<dyn Fn(&T) -> String + Send + Sync>),
+}
+
+impl<T> BackpressurePolicy<T> {
+    /// 키 추출 함수로 `ConflateByKey` 정책을 만듭니다.
+    pub fn conflate_by<K>(key: K) -> Self
+    where
+        K: Fn(&T) -> String + Send + Sync + 'static,
+    {
+        BackpressurePolicy::ConflateByKey(Arc::new(key))
+    }
+}
+
+impl<T> Clone for BackpressurePolicy<T> {
+    fn clone(&self) -> Self {
+        match self {
+            BackpressurePolicy::DropOldest => BackpressurePolicy::DropOldest,
+            BackpressurePolicy::Block => BackpressurePolicy::Block,
+            BackpressurePolicy::ConflateByKey(key) => {
+                BackpressurePolicy::ConflateByKey(key.clone())
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for BackpressurePolicy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackpressurePolicy::DropOldest => write!(f, "DropOldest"),
+            BackpressurePolicy::Block => write!(f, "Block"),
+            BackpressurePolicy::ConflateByKey(_) => write!(f, "ConflateByKey"),
+        }
+    }
+}
+
+struct Buffer<T> {
+    items: VecDeque<(Option<String>, T)>,
+    receiver_closed: bool,
+    sender_closed: bool,
+}
+
+struct Shared<T> {
+    buffer: Mutex<Buffer<T>>,
+    capacity: usize,
+    policy: BackpressurePolicy<T>,
+    readable: Notify,
+    writable: Notify,
+    dropped: AtomicU64,
+}
+
+/// 정책 기반 bounded 채널 생성 (capacity는 최소 1)
+pub fn channel<T>(
+    capacity: usize,
+    policy: BackpressurePolicy<T>,
+) -> (PolicySender<T>, PolicyReceiver<T>) {
+    let shared = Arc::new(Shared {
+        buffer: Mutex::new(Buffer {
+            items: VecDeque::with_capacity(capacity.max(1)),
+            receiver_closed: false,
+            sender_closed: false,
+        }),
+        capacity: capacity.max(1),
+        policy,
+        readable: Notify::new(),
+        writable: Notify::new(),
+        dropped: AtomicU64::new(0),
+    });
+    (
+        PolicySender {
+            shared: shared.clone(),
+        },
+        PolicyReceiver { shared },
+    )
+}
+
+/// 채널 송신측
+pub struct PolicySender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> PolicySender<T> {
+    /// 메시지를 넣습니다. 수신측이 닫혔으면 메시지를 돌려줍니다.
+    pub async fn send(&self, msg: T) -> Result<(), T> {
+        let key = match &self.shared.policy {
+            BackpressurePolicy::ConflateByKey(key_fn) => Some(key_fn(&msg)),
+            _ => None,
+        };
+        let mut msg = Some(msg);
+        loop {
+            {
+                let mut buffer = self.shared.buffer.lock().unwrap();
+                if buffer.receiver_closed {
+                    return Err(msg.take().unwrap());
+                }
+                if let Some(k) = &key
+                    && let Some(slot) = buffer
+                        .items
+                        .iter_mut()
+                        .find(|(existing, _)| existing.as_ref() == Some(k))
+                {
+                    slot.1 = msg.take().unwrap();
+                    self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                    self.shared.readable.notify_one();
+                    return Ok(());
+                }
+                if buffer.items.len() < self.shared.capacity {
+                    buffer.items.push_back((key, msg.take().unwrap()));
+                    self.shared.readable.notify_one();
+                    return Ok(());
+                }
+                if !matches!(self.shared.policy, BackpressurePolicy::Block) {
+                    buffer.items.pop_front();
+                    buffer.items.push_back((key, msg.take().unwrap()));
+                    self.shared.dropped.fetch_add(1, Ordering::Relaxed);
+                    self.shared.readable.notify_one();
+                    return Ok(());
+                }
+            }
+            // Block: 소비자가 꺼낼 때까지 대기
+            self.shared.writable.notified().await;
+        }
+    }
+
+    /// 정책에 의해 버려지거나 덮어써진 메시지 수
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Drop for PolicySender<T> {
+    fn drop(&mut self) {
+        self.shared.buffer.lock().unwrap().sender_closed = true;
+        self.shared.readable.notify_one();
+    }
+}
+
+/// 채널 수신측
+pub struct PolicyReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> PolicyReceiver<T> {
+    /// 다음 메시지를 기다립니다. 송신측이 닫히고 버퍼가 비면 None을 돌려줍니다.
+    pub async fn recv(&mut self) -> Option<T> {
+        loop {
+            {
+                let mut buffer = self.shared.buffer.lock().unwrap();
+                if let Some((_, msg)) = buffer.items.pop_front() {
+                    self.shared.writable.notify_one();
+                    return Some(msg);
+                }
+                if buffer.sender_closed {
+                    return None;
+                }
+            }
+            self.shared.readable.notified().await;
+        }
+    }
+
+    /// 정책에 의해 버려지거나 덮어써진 메시지 수
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    pub fn into_stream(self) -> BoxStream<'static, T>
+    where
+        T: Send + 'static,
+    {
+        Box::pin(futures::stream::unfold(self, |mut rx| async move {
+            rx.recv().await.map(|msg| (msg, rx))
+        }))
+    }
+}
+
+impl<T> Drop for PolicyReceiver<T> {
+    fn drop(&mut self) {
+        self.shared.buffer.lock().unwrap().receiver_closed = true;
+        self.shared.writable.notify_one();
+    }
+}
+
+/// `WebSocketClient::into_stream()`이 반환하는 메시지 스트림
+///
+/// 백그라운드 태스크에서 클라이언트를 실행하며, 스트림을 drop하면 태스크도 중단됩니다.
+/// 스트림이 끝나면 `join()`으로 종료 사유를 확인하세요.
+pub struct ClientStream<T> {
+    inner: BoxStream<'static, T>,
+    shared: Arc<Shared<T>>,
+    task: Option<JoinHandle<Result<(), ClientError>>>,
+}
+
+impl<T> ClientStream<T> {
+    /// 정책에 의해 버려지거나 덮어써진 메시지 수
+    pub fn dropped(&self) -> u64 {
+        self.shared.dropped.load(Ordering::Relaxed)
+    }
+
+    /// 클라이언트 태스크가 종료(재연결 한도 초과 등)되었는지 여부
+    pub fn is_finished(&self) -> bool {
+        self.task.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+
+    /// 클라이언트 태스크가 끝날 때까지 기다리고 종료 사유를 돌려줍니다.
+    ///
+    /// 스트림이 `None`으로 끝난 뒤 호출하면 정상 종료인지, 재연결 한도 초과나 인증 실패 같은 에러인지 알 수 있습니다.
+    pub async fn join(mut self) -> Result<(), ClientError> {
+        let Some(task) = self.task.take() else {
+            return Ok(());
+        };
+        match task.await {
+            Ok(result) => result,
+            Err(e) => Err(ClientError::Fatal(format!("클라이언트 태스크 중단: {}", e))),
+        }
+    }
+}
+
+impl<T> Stream for ClientStream<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl<T> Drop for ClientStream<T> {
+    fn drop(&mut self) {
+        if let Some(task) = &self.task {
+            task.abort();
+        }
+    }
+}
+
+impl<H> WebSocketClient<H>
+where
+    H: MessageHandler + 'static,
+    H::Message: 'static,
+{
+    /// 클라이언트를 백그라운드 태스크에서 실행하고 수신 메시지를 `Stream`으로 돌려줍니다.
+    ///
+    /// `capacity`는 버퍼 크기, `policy`는 버퍼가 가득 찼을 때의 처리 방식입니다.
+    /// 런타임 구독 변경이 필요하면 호출 전에 `subscription_handle()`을 받아 두세요.
+    pub fn into_stream(
+        self,
+        capacity: usize,
+        policy: BackpressurePolicy<H::Message>,
+    ) -> ClientStream<H::Message> {
+        let (tx, rx) = channel(capacity, policy);
+        let shared = rx.shared.clone();
+        let task = tokio::spawn(async move {
+            self.run(|msg| {
+                let tx = &tx;
+                async move {
+                    let _ = tx.send(msg).await;
+                }
+            })
+            .await
+        });
+        ClientStream {
+            inner: rx.into_stream(),
+            shared,
+            task: Some(task),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::websocket::client::ClientConfig;
+    use futures::{SinkExt, StreamExt};
+    use serde::{Deserialize, Serialize};
+    use serde_json::Value;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::Message as TungsteniteMessage;
+
+    async fn drain(mut rx: PolicyReceiver<(String, u32)>) -> Vec<(String, u32)> {
+        let mut out = Vec::new();
+        while let Some(msg) = rx.recv().await {
+            out.push(msg);
+        }
+        out
+    }
+
+    fn msg(key: &str, v: u32) -> (String, u32) {
+        (key.to_string(), v)
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest() {
+        let (tx, rx) = channel(2, BackpressurePolicy::DropOldest);
+        for v in 0..4 {
+            tx.send(msg("A", v)).await.unwrap();
+        }
+        assert_eq!(tx.dropped(), 2);
+        drop(tx);
+        assert_eq!(drain(rx).await, vec![msg("A", 2), msg("A", 3)]);
+    }
+
+    #[tokio::test]
+    async fn test_conflate_by_key() {
+        let (tx, rx) = channel(
+            2,
+            BackpressurePolicy::conflate_by(|m: &(String, u32)| m.0.clone()),
+        );
+        tx.send(msg("A", 1)).await.unwrap();
+        tx.send(msg("B", 1)).await.unwrap();
+        tx.send(msg("A", 2)).await.unwrap();
+        // 새 키 C가 들어오면 가장 오래된 키 A가 밀려남
+        tx.send(msg("C", 1)).await.unwrap();
+        drop(tx);
+        assert_eq!(drain(rx).await, vec![msg("B", 1), msg("C", 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_block_waits_for_consumer() {
+        let (tx, mut rx) = channel(1, BackpressurePolicy::Block);
+        tx.send(msg("A", 1)).await.unwrap();
+
+        let producer = tokio::spawn(async move {
+            tx.send(msg("A", 2)).await.unwrap();
+            tx.dropped()
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(
+            !producer.is_finished(),
+            "버퍼가 가득 차면 send가 대기해야 함"
+        );
+
+        assert_eq!(rx.recv().await, Some(msg("A", 1)));
+        assert_eq!(producer.await.unwrap(), 0);
+        assert_eq!(rx.recv().await, Some(msg("A", 2)));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_send_fails_after_receiver_dropped() {
+        let (tx, rx) = channel(1, BackpressurePolicy::Block);
+        tx.send(msg("A", 1)).await.unwrap();
+        drop(rx);
+        assert_eq!(tx.send(msg("A", 2)).await, Err(msg("A", 2)));
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Tick {
+        shcode: String,
+        price: String,
+    }
+
+    struct TickHandler;
+
+    impl MessageHandler for TickHandler {
+        type Message = Tick;
+
+        fn subscription_message(&self) -> Value {
+            serde_json::json!({
+                "header": {"token": "test-token", "tr_type": "3"},
+                "body": {"tr_cd": "UH1", "tr_key": "005930"}
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_into_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = ClientConfig {
+            url: format!("ws://{}", listener.local_addr().unwrap()),
+            ..ClientConfig::default()
+        };
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            ws.next().await;
+            for price in ["00071000", "00071100"] {
+                let frame = serde_json::json!({
+                    "header": {"tr_cd": "UH1", "tr_key": "005930"},
+                    "body": {"shcode": "005930", "price": price}
+                });
+                ws.send(TungsteniteMessage::Text(frame.to_string()))
+                    .await
+                    .unwrap();
+            }
+            // 스트림이 끝날 때까지 연결 유지
+            while ws.next().await.is_some() {}
+        });
+
+        let client = WebSocketClient::new(config, TickHandler);
+        let stream = client.into_stream(16, BackpressurePolicy::Block);
+        let prices: Vec<String> = stream.take(2).map(|tick| tick.price).collect().await;
+        assert_eq!(prices, vec!["00071000", "00071100"]);
+    }
+
+    #[tokio::test]
+    async fn test_join_reports_terminal_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = ClientConfig {
+            url: format!("ws://{}", listener.local_addr().unwrap()),
+            max_fatal_failures: 1,
+            ..ClientConfig::default()
+        };
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            ws.next().await;
+            let reply = r#"{"header":{"tr_cd":"UH1","tr_key":"005930","rsp_cd":"IGW00121","rsp_msg":"유효하지 않은 토큰입니다"},"body":null}"#;
+            ws.send(TungsteniteMessage::Text(reply.to_string()))
+                .await
+                .unwrap();
+            while ws.next().await.is_some() {}
+        });
+
+        let mut stream =
+            WebSocketClient::new(config, TickHandler).into_stream(16, BackpressurePolicy::Block);
+        assert!(stream.next().await.is_none());
+        assert!(stream.is_finished());
+        let err = stream.join().await.unwrap_err();
+        assert!(matches!(err, ClientError::Fatal(_)), "{}", err);
+    }
+}
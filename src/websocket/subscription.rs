@@ -0,0 +1,188 @@
+// 하나의 WebSocket 연결에서 실시간 시세를 런타임에 등록/해제하기 위한 명령 핸들과 구독 상태
+
+use crate::constant::{
+    LS_WS_TR_TYPE_ACCOUNT_REGISTER, LS_WS_TR_TYPE_ACCOUNT_UNREGISTER, LS_WS_TR_TYPE_REGISTER,
+    LS_WS_TR_TYPE_UNREGISTER,
+};
+use crate::types::tr_code::TrCode;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+/// 실시간 구독 단위 (tr_cd + tr_key)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Subscription {
+    pub tr_cd: String,
+    pub tr_key: String,
+}
+
+impl Subscription {
+    pub fn new(tr_cd: impl Into<String>, tr_key: impl Into<String>) -> Self {
+        Self {
+            tr_cd: tr_cd.into(),
+            tr_key: tr_key.into(),
+        }
+    }
+
+    /// `{"header": {...}, "body": {"tr_cd", "tr_key"}}` 형식의 요청 메시지에서 구독 정보를 추출합니다.
+    pub fn from_request(request: &Value) -> Option<Self> {
+        let body = request.get("body")?;
+        Some(Self::new(
+            body.get("tr_cd")?.as_str()?,
+            body.get("tr_key")?.as_str()?,
+        ))
+    }
+}
+
+/// 실행 중인 클라이언트에 전달되는 구독 명령
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionCommand {
+    /// 실시간 시세 등록 (tr_type "3")
+    Subscribe(Subscription),
+    /// 실시간 시세 해제 (tr_type "4")
+    Unsubscribe(Subscription),
+}
+
+impl SubscriptionCommand {
+    /// 요청 tr_type. 계좌 단위 TR(SC0~SC4)은 계좌 등록/해제("1"/"2")를 사용합니다.
+    pub fn tr_type(&self) -> &'static str {
+        let account_scoped = self
+            .subscription()
+            .tr_cd
+            .parse::<TrCode>()
+            .is_ok_and(|code| code.is_account_scoped());
+        match (self, account_scoped) {
+            (SubscriptionCommand::Subscribe(_), false) => LS_WS_TR_TYPE_REGISTER,
+            (SubscriptionCommand::Unsubscribe(_), false) => LS_WS_TR_TYPE_UNREGISTER,
+            (SubscriptionCommand::Subscribe(_), true) => LS_WS_TR_TYPE_ACCOUNT_REGISTER,
+            (SubscriptionCommand::Unsubscribe(_), true) => LS_WS_TR_TYPE_ACCOUNT_UNREGISTER,
+        }
+    }
+
+    pub fn subscription(&self) -> &Subscription {
+        match self {
+            SubscriptionCommand::Subscribe(sub) | SubscriptionCommand::Unsubscribe(sub) => sub,
+        }
+    }
+}
+
+/// 클라이언트가 현재 서버에 등록해 둔 구독 목록
+pub type ActiveSubscriptions = Arc<Mutex<BTreeSet<Subscription>>>;
+
+/// 실행 중인 `WebSocketClient`에 등록/해제 명령을 보내는 핸들 (clone 가능)
+#[derive(Debug, Clone)]
+pub struct SubscriptionHandle {
+    tx: mpsc::UnboundedSender<SubscriptionCommand>,
+    active: ActiveSubscriptions,
+}
+
+impl SubscriptionHandle {
+    pub(crate) fn new(
+        tx: mpsc::UnboundedSender<SubscriptionCommand>,
+        active: ActiveSubscriptions,
+    ) -> Self {
+        Self { tx, active }
+    }
+
+    /// 실시간 시세 등록 요청. 연결이 끊겨 있으면 다음 연결 시 전송됩니다.
+    pub fn subscribe(
+        &self,
+        tr_cd: impl Into<String>,
+        tr_key: impl Into<String>,
+    ) -> Result<(), SubscriptionError> {
+        self.send(SubscriptionCommand::Subscribe(Subscription::new(
+            tr_cd, tr_key,
+        )))
+    }
+
+    /// 실시간 시세 해제 요청
+    pub fn unsubscribe(
+        &self,
+        tr_cd: impl Into<String>,
+        tr_key: impl Into<String>,
+    ) -> Result<(), SubscriptionError> {
+        self.send(SubscriptionCommand::Unsubscribe(Subscription::new(
+            tr_cd, tr_key,
+        )))
+    }
+
+    pub fn send(&self, command: SubscriptionCommand) -> Result<(), SubscriptionError> {
+        self.tx
+            .send(command)
+            .map_err(|_| SubscriptionError::ClientClosed)
+    }
+
+    /// 현재 서버에 등록된 구독 목록 스냅샷
+    pub fn active(&self) -> Vec<Subscription> {
+        self.active.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn is_active(&self, tr_cd: &str, tr_key: &str) -> bool {
+        self.active
+            .lock()
+            .unwrap()
+            .contains(&Subscription::new(tr_cd, tr_key))
+    }
+}
+
+/// 구독 명령 전송 에러
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionError {
+    /// 클라이언트가 종료되어 명령을 받을 수 없음
+    ClientClosed,
+}
+
+impl std::fmt::Display for SubscriptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubscriptionError::ClientClosed => write!(f, "WebSocket 클라이언트가 종료되었습니다"),
+        }
+    }
+}
+
+impl std::error::Error for SubscriptionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_request() {
+        let request = serde_json::json!({
+            "header": {"token": "t", "tr_type": "3"},
+            "body": {"tr_cd": "UH1", "tr_key": "005930"}
+        });
+        assert_eq!(
+            Subscription::from_request(&request),
+            Some(Subscription::new("UH1", "005930"))
+        );
+        assert_eq!(Subscription::from_request(&Value::Null), None);
+    }
+
+    #[test]
+    fn test_handle_sends_commands() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let handle = SubscriptionHandle::new(tx, ActiveSubscriptions::default());
+
+        handle.subscribe("UH1", "005930").unwrap();
+        handle.unsubscribe("UH1", "005930").unwrap();
+
+        let first = rx.try_recv().unwrap();
+        assert_eq!(first.tr_type(), LS_WS_TR_TYPE_REGISTER);
+        assert_eq!(first.subscription(), &Subscription::new("UH1", "005930"));
+        assert_eq!(rx.try_recv().unwrap().tr_type(), LS_WS_TR_TYPE_UNREGISTER);
+
+        handle.subscribe(TrCode::OrderExecution, "").unwrap();
+        assert_eq!(
+            rx.try_recv().unwrap().tr_type(),
+            LS_WS_TR_TYPE_ACCOUNT_REGISTER
+        );
+
+        drop(rx);
+        assert_eq!(
+            handle.subscribe("UH1", "000660"),
+            Err(SubscriptionError::ClientClosed)
+        );
+    }
+}
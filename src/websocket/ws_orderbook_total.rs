@@ -1,20 +1,17 @@
 use crate::types::orderbook::OrderbookMessage;
+use crate::types::tr_code::TrCode;
 use crate::websocket::client::{ClientConfig, WebSocketClient};
 use crate::websocket::handler::MessageHandler;
 use crate::zmq::publisher::ZmqPublisher;
-use futures_util::StreamExt;
 use serde_json::Value;
 use std::error::Error;
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::time::Duration;
-use tokio_tungstenite::tungstenite::Message as WsMessage;
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message}; // for .next()
 
 #[derive(Debug, Clone)]
 pub struct OrderbookHandlerConfig {
     pub token: String,
-    pub tr_cd: String, // <- 시장별로 "H1_", "HA_", "UH1" 등 지정
+    pub tr_cd: TrCode, // <- 시장별로 H1_, HA_, UH1 등 지정
     pub tr_key: String,
     pub zmq_endpoint: String,
     pub print_console: bool,
@@ -26,7 +23,7 @@ impl Default for OrderbookHandlerConfig {
     fn default() -> Self {
         Self {
             token: "".to_string(),
-            tr_cd: "UH1".to_string(), // 기본값: KOSPI
+            tr_cd: TrCode::UniOrderbook, // 기본값: 통합 호가잔량
             tr_key: "".to_string(),
             zmq_endpoint: "tcp://0.0.0.0:5557".to_string(),
             print_console: true,
@@ -86,12 +83,11 @@ pub async fn run_orderbook_stream(
             if handler_config.print_console {
                 println!("{:?}", orderbook);
             }
-            if handler_config.save_to_file {
-                if let Some(ref path) = handler_config.file_path {
-                    if let Err(e) = save_orderbook(orderbook, path) {
-                        eprintln!("Orderbook 데이터 저장 실패: {}", e);
-                    }
-                }
+            if handler_config.save_to_file
+                && let Some(ref path) = handler_config.file_path
+                && let Err(e) = save_orderbook(orderbook, path)
+            {
+                eprintln!("Orderbook 데이터 저장 실패: {}", e);
             }
             let json = serde_json::to_string(orderbook).unwrap();
             if let Err(e) = publisher.send(json.as_str()) {
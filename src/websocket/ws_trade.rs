@@ -0,0 +1,100 @@
+use crate::types::tr_code::TrCode;
+use crate::types::trade::TradeMessage;
+use crate::websocket::client::{ClientConfig, WebSocketClient};
+use crate::websocket::handler::MessageHandler;
+use crate::zmq::publisher::ZmqPublisher;
+use serde_json::Value;
+use std::error::Error;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+#[derive(Debug, Clone)]
+pub struct TradeHandlerConfig {
+    pub token: String,
+    pub tr_cd: TrCode, // <- 시장별로 S3_, K3_, US3, NS3 등 지정
+    pub tr_key: String,
+    pub zmq_endpoint: String,
+    pub print_console: bool,
+    pub save_to_file: bool,
+    pub file_path: Option<String>,
+}
+
+impl Default for TradeHandlerConfig {
+    fn default() -> Self {
+        Self {
+            token: "".to_string(),
+            tr_cd: TrCode::UniExecution, // 기본값: 통합 체결
+            tr_key: "".to_string(),
+            zmq_endpoint: "tcp://0.0.0.0:5558".to_string(),
+            print_console: true,
+            save_to_file: false,
+            file_path: None,
+        }
+    }
+}
+
+pub struct TradeHandler {
+    config: TradeHandlerConfig,
+}
+
+impl TradeHandler {
+    pub fn new(config: TradeHandlerConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl MessageHandler for TradeHandler {
+    type Message = TradeMessage;
+
+    fn subscription_message(&self) -> Value {
+        serde_json::json!({
+            "header": {
+                "token": self.config.token,
+                "tr_type": "3", // 실시간 시세 등록
+            },
+            "body": {
+                "tr_cd": self.config.tr_cd,
+                "tr_key": self.config.tr_key,
+            }
+        })
+    }
+}
+
+fn save_trade(trade: &TradeMessage, file_path: &str) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(file_path)?;
+    writeln!(file, "{:?}", trade)?;
+    Ok(())
+}
+
+pub async fn run_trade_stream(
+    client_config: ClientConfig,
+    handler_config: TradeHandlerConfig,
+) -> Result<(), Box<dyn Error>> {
+    let handler = TradeHandler::new(handler_config.clone());
+    let client = WebSocketClient::new(client_config, handler);
+
+    let publisher = ZmqPublisher::bind(&handler_config.zmq_endpoint)?;
+
+    client
+        .run_with_callback(move |trade: &TradeMessage| {
+            if handler_config.print_console {
+                println!("{:?}", trade);
+            }
+            if handler_config.save_to_file
+                && let Some(ref path) = handler_config.file_path
+                && let Err(e) = save_trade(trade, path)
+            {
+                eprintln!("Trade 데이터 저장 실패: {}", e);
+            }
+            let json = serde_json::to_string(trade).unwrap();
+            if let Err(e) = publisher.send(json.as_str()) {
+                eprintln!("ZeroMQ publish 실패: {}", e);
+            }
+        })
+        .await?;
+
+    Ok(())
+}
@@ -1,36 +1,25 @@
-use serde::Deserialize;
-use std::fs;
-
-use xing_trading_rust::websocket::client::ClientConfig;
+use xing_trading_rust::auth::oauth::get_access_token;
+use xing_trading_rust::config::AppConfig;
+use xing_trading_rust::types::tr_code::TrCode;
+use xing_trading_rust::types::tr_key::TrKey;
 use xing_trading_rust::websocket::ws_orderbook_total::{
     OrderbookHandlerConfig, run_orderbook_stream,
 };
 
-#[derive(Deserialize)]
-struct TokenCache {
-    access_token: String,
-    expired_at: String,
-}
-
-fn load_token_from_file(path: &str) -> String {
-    let data = fs::read_to_string(path).expect("토큰 파일 읽기 실패");
-    let cache: TokenCache = serde_json::from_str(&data).expect("JSON 파싱 실패");
-    cache.access_token
-}
-
 #[tokio::test]
 async fn test_real_orderbook_stream() {
-    let token = load_token_from_file("kis_token_cache.json");
-    let client_config = ClientConfig {
-        url: "wss://openapi.ls-sec.co.kr:9443/websocket".to_string(),
-        reconnect_interval: std::time::Duration::from_secs(5),
-        max_reconnect_attempts: 10,
-        ping_interval: std::time::Duration::from_secs(60),
-    };
+    let config = AppConfig::from_env();
+    let client = config.http_client().expect("HTTP 클라이언트 생성 실패");
+    let token = get_access_token(&client, &config)
+        .await
+        .expect("토큰 발급 실패");
+    let client_config = config.client_config();
     let handler_config = OrderbookHandlerConfig {
         token,
-        tr_cd: "UH1".to_string(),         // 실제 사용 값
-        tr_key: "U005930   ".to_string(), // 실제 사용 값 (공백 포함)
+        tr_cd: TrCode::UniOrderbook, // 실제 사용 값
+        tr_key: TrKey::for_symbol(TrCode::UniOrderbook, "005930")
+            .unwrap()
+            .into_string(), // "U005930   " (공백 포함)
         print_console: true,
         save_to_file: false,
         file_path: None,